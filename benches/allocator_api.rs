@@ -428,6 +428,22 @@ where
         })
     });
 
+    // Only room for one `Vec` at a time: each one is dropped before the next
+    // is created, so an allocator that reclaims deallocated tips never needs
+    // more memory than that, and `Bumpalo`'s allocation limit would fail the
+    // benchmark if it did.
+    group.bench_function(format!("push/pop Vec(16 x usize) x {NUM_ALLOCS}"), |b| {
+        let mut alloc = A::with_capacity(mem::size_of::<usize>() * 16);
+        b.iter(|| {
+            for i in 0..NUM_ALLOCS {
+                let mut vec = Vec::with_capacity_in(16, &alloc);
+                vec.extend(i..i + 16);
+                black_box(&vec);
+            }
+            alloc.reset();
+        })
+    });
+
     group.bench_function(format!("reserve_exact(1) x {NUM_ALLOCS}"), |b| {
         let mut alloc = A::with_capacity(mem::size_of::<usize>() * NUM_ALLOCS * RESIZE_FACTOR);
         b.iter(|| {
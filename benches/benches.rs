@@ -15,6 +15,16 @@ fn alloc<T: Default>(n: usize) {
     }
 }
 
+fn alloc_min_align<const MIN_ALIGN: usize>(n: usize) {
+    let arena =
+        bumpalo::Bump::<MIN_ALIGN>::with_min_align_and_capacity(n * std::mem::size_of::<u64>());
+    for _ in 0..n {
+        let arena = black_box(&arena);
+        let val: &mut u64 = arena.alloc(black_box(0));
+        black_box(val);
+    }
+}
+
 fn alloc_with<T: Default>(n: usize) {
     let arena = bumpalo::Bump::with_capacity(n * std::mem::size_of::<T>());
     for _ in 0..n {
@@ -143,6 +153,7 @@ fn bench_extend_from_slice_copy(c: &mut Criterion) {
         1024,
         4 * 1024,
         16 * 1024,
+        1024 * 1024,
     ];
 
     for len in lengths.iter().copied() {
@@ -279,6 +290,17 @@ fn bench_alloc_try_with(c: &mut Criterion) {
     });
 }
 
+fn bench_alloc_min_align(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alloc-min-align");
+    group.throughput(Throughput::Elements(ALLOCATIONS as u64));
+    group.bench_function("u64, MIN_ALIGN = 1", |b| {
+        b.iter(|| alloc_min_align::<1>(ALLOCATIONS))
+    });
+    group.bench_function("u64, MIN_ALIGN = 8", |b| {
+        b.iter(|| alloc_min_align::<8>(ALLOCATIONS))
+    });
+}
+
 fn bench_alloc_try_with_err(c: &mut Criterion) {
     let mut group = c.benchmark_group("alloc-try-with-err");
     group.throughput(Throughput::Elements(ALLOCATIONS as u64));
@@ -389,11 +411,80 @@ fn bench_string_push_str(c: &mut Criterion) {
     });
 }
 
+fn bench_alloc_slice_clone_copy(c: &mut Criterion) {
+    let len: usize = 64 * 1024;
+    let src: Vec<u32> = (0..len as u32).collect();
+
+    let mut group = c.benchmark_group("alloc-slice-u32");
+    group.throughput(Throughput::Elements(len as u64));
+    group.bench_function("alloc_slice_clone", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len * 4);
+        b.iter(|| {
+            bump.reset();
+            black_box(bump.alloc_slice_clone(black_box(&src[..])));
+        });
+    });
+    group.bench_function("alloc_slice_copy", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len * 4);
+        b.iter(|| {
+            bump.reset();
+            black_box(bump.alloc_slice_copy(black_box(&src[..])));
+        });
+    });
+}
+
+fn bench_alloc_slice_fill_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alloc-slice-fill-copy");
+
+    let len: usize = 4 * 1024 * 1024;
+    group.throughput(Throughput::Bytes(len as u64));
+    group.bench_function("u8 x 4MiB", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len);
+        b.iter(|| {
+            bump.reset();
+            black_box(bump.alloc_slice_fill_copy(black_box(len), black_box(0x5a_u8)));
+        });
+    });
+
+    let len: usize = 1024 * 1024;
+    group.throughput(Throughput::Bytes(len as u64 * 8));
+    group.bench_function("u64 x 1M", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len * 8);
+        b.iter(|| {
+            bump.reset();
+            black_box(bump.alloc_slice_fill_copy(black_box(len), black_box(u64::MAX / 3)));
+        });
+    });
+}
+
+fn bench_collect_filtered(c: &mut Criterion) {
+    let len: u64 = 10_000;
+    let mut group = c.benchmark_group("collect-filtered-u64");
+    group.throughput(Throughput::Elements(len));
+    group.bench_function("try_collect_slice", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len as usize * 8);
+        b.iter(|| {
+            bump.reset();
+            let iter = (0..black_box(len)).filter(|n| n % 3 != 0);
+            black_box(bump.try_collect_slice(iter).unwrap());
+        });
+    });
+    group.bench_function("Vec::from_iter_in", |b| {
+        let mut bump = bumpalo::Bump::with_capacity(len as usize * 8);
+        b.iter(|| {
+            bump.reset();
+            let iter = (0..black_box(len)).filter(|n| n % 3 != 0);
+            black_box(bumpalo::collections::Vec::from_iter_in(iter, &bump).into_bump_slice());
+        });
+    });
+}
+
 criterion_group!(
     benches,
     bench_extend_from_slice_copy,
     bench_extend_from_slices_copy,
     bench_alloc,
+    bench_alloc_min_align,
     bench_alloc_with,
     bench_alloc_try_with,
     bench_alloc_try_with_err,
@@ -403,6 +494,9 @@ criterion_group!(
     bench_try_alloc_try_with_err,
     bench_format_realloc,
     bench_string_from_str_in,
-    bench_string_push_str
+    bench_string_push_str,
+    bench_alloc_slice_clone_copy,
+    bench_alloc_slice_fill_copy,
+    bench_collect_filtered
 );
 criterion_main!(benches);
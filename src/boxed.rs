@@ -93,6 +93,35 @@
 //! for a `Cons`. By introducing a [`Box<'a, T>`], which has a defined size, we know how
 //! big `Cons` needs to be.
 //!
+//! # Trait objects
+//!
+//! Unsizing coercions such as `Box<'a, T>` to `Box<'a, dyn Trait>` require the
+//! unstable `CoerceUnsized` trait, so they do not happen implicitly for this
+//! [`Box`]. Instead, cast the raw pointer, which performs the same unsizing
+//! coercion and is checked by the compiler:
+//!
+//! ```
+//! use bumpalo::{Bump, boxed::Box};
+//! use std::fmt::Display;
+//!
+//! let b = Bump::new();
+//!
+//! let boxed = Box::new_in(42, &b);
+//! let boxed: Box<dyn Display> = unsafe { Box::from_raw(Box::into_raw(boxed) as *mut dyn Display) };
+//! assert_eq!(boxed.to_string(), "42");
+//! ```
+//!
+//! If the value does not need to be dropped, a plain reference returned by
+//! [`Bump::alloc`] coerces to a trait object without any extra steps:
+//!
+//! ```
+//! use std::fmt::Display;
+//!
+//! let b = bumpalo::Bump::new();
+//! let x: &mut dyn Display = b.alloc(42);
+//! assert_eq!(x.to_string(), "42");
+//! ```
+//!
 //! # Memory layout
 //!
 //! For non-zero-sized values, a [`Box`] will use the provided [`Bump`] allocator for
@@ -172,6 +201,35 @@ impl<'a, T> Box<'a, T> {
         Box(a.alloc(x)).into()
     }
 
+    /// Clones the boxed value into a new `Box` in the given arena.
+    ///
+    /// `Box` does not implement `Clone`, because it does not keep a reference
+    /// to the `Bump` it was allocated in, so the arena has to be passed in
+    /// explicitly. It may be the same arena as `b`'s, or a different one.
+    ///
+    /// This is an associated function, so that it does not shadow methods on
+    /// the boxed value; call it as `Box::clone_in(&b, &bump)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let x = Box::new_in(vec![1, 2], &b);
+    /// let mut y = Box::clone_in(&x, &b);
+    /// y.push(3);
+    /// assert_eq!(*x, [1, 2]);
+    /// assert_eq!(*y, [1, 2, 3]);
+    /// ```
+    pub fn clone_in<'b>(b: &Box<'a, T>, a: &'b Bump) -> Box<'b, T>
+    where
+        T: Clone,
+    {
+        Box(a.alloc_with(|| (**b).clone()))
+    }
+
     /// Consumes the `Box`, returning the wrapped value.
     ///
     /// # Examples
@@ -296,6 +354,11 @@ impl<'a, T: ?Sized> Box<'a, T> {
     /// then be dropped which will properly destroy `T` and release the
     /// allocated memory.
     ///
+    /// After leaking, `T`'s destructor will **not** be run, neither when the
+    /// returned reference goes out of scope nor when the [`Bump`] is reset or
+    /// dropped. The value simply lives on in the arena, exactly as if it had
+    /// been allocated with [`Bump::alloc`].
+    ///
     /// Note: this is an associated function, which means that you have
     /// to call it as `Box::leak(b)` instead of `b.leak()`. This
     /// is so that there is no conflict with a method on the inner type.
@@ -682,6 +745,64 @@ impl<'a, T, const N: usize> TryFrom<Box<'a, [T]>> for Box<'a, [T; N]> {
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<'a, T: ?Sized> Box<'a, T> {
+    /// Converts a `bumpalo::boxed::Box` into a [`std::boxed::Box`] that uses
+    /// `bump` as its allocator.
+    ///
+    /// The value is not moved or copied: the returned box points at the same
+    /// memory.
+    ///
+    /// Note: this is an associated function, which means that you have to call
+    /// it as `Box::into_std(b, bump)` instead of `b.into_std(bump)`. This is so
+    /// that there is no conflict with a method on the inner type.
+    ///
+    /// # Safety
+    ///
+    /// `b` must have been allocated in `bump`. The returned box hands its
+    /// memory back to `bump` when it is dropped, and the [`Allocator`] contract
+    /// requires that memory to belong to that allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let bump = Bump::new();
+    /// let b = Box::new_in(5, &bump);
+    /// let std_box = unsafe { Box::into_std(b, &bump) };
+    /// assert_eq!(*std_box, 5);
+    /// ```
+    ///
+    /// [`std::boxed::Box`]: https://doc.rust-lang.org/std/boxed/struct.Box.html
+    /// [`Allocator`]: https://doc.rust-lang.org/std/alloc/trait.Allocator.html
+    #[inline]
+    pub unsafe fn into_std<const MIN_ALIGN: usize>(
+        b: Box<'a, T>,
+        bump: &'a Bump<MIN_ALIGN>,
+    ) -> core_alloc::boxed::Box<T, &'a Bump<MIN_ALIGN>> {
+        core_alloc::boxed::Box::from_raw_in(Box::into_raw(b), bump)
+    }
+}
+
+/// Converts a [`std::boxed::Box`] allocated in a `Bump` into a
+/// `bumpalo::boxed::Box`, without moving the value.
+///
+/// The returned box still runs the value's destructor when dropped, but, like
+/// every `bumpalo::boxed::Box`, does not hand its memory back to the arena.
+///
+/// [`std::boxed::Box`]: https://doc.rust-lang.org/std/boxed/struct.Box.html
+#[cfg(feature = "allocator_api")]
+impl<'a, T: ?Sized, const MIN_ALIGN: usize> From<core_alloc::boxed::Box<T, &'a Bump<MIN_ALIGN>>>
+    for Box<'a, T>
+{
+    fn from(b: core_alloc::boxed::Box<T, &'a Bump<MIN_ALIGN>>) -> Box<'a, T> {
+        Box(core_alloc::boxed::Box::leak(b))
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serialize {
     use super::*;
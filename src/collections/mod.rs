@@ -21,7 +21,7 @@ pub use self::vec::Vec;
 
 mod str;
 pub mod string;
-pub use self::string::String;
+pub use self::string::{String, ToBumpString};
 
 mod collect_in;
 pub use collect_in::{CollectIn, FromIteratorIn};
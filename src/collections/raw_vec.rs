@@ -17,7 +17,7 @@ use core::cmp;
 use core::mem;
 use core::ptr::{self, NonNull};
 
-use crate::alloc::{handle_alloc_error, Alloc, Layout, UnstableLayoutMethods};
+use crate::alloc::{Alloc, Layout, UnstableLayoutMethods};
 use crate::collections::CollectionAllocErr;
 use crate::collections::CollectionAllocErr::*;
 // use boxed::Box;
@@ -104,7 +104,7 @@ impl<'a, T> RawVec<'a, T> {
                 };
                 match result {
                     Ok(ptr) => ptr.cast(),
-                    Err(_) => handle_alloc_error(layout),
+                    Err(_) => a.handle_alloc_error(layout),
                 }
             };
 
@@ -245,7 +245,7 @@ impl<'a, T> RawVec<'a, T> {
                     let ptr_res = self.a.realloc(self.ptr.cast(), cur, new_size);
                     match ptr_res {
                         Ok(ptr) => (new_cap, ptr.cast()),
-                        Err(_) => handle_alloc_error(Layout::from_size_align_unchecked(
+                        Err(_) => self.a.handle_alloc_error(Layout::from_size_align_unchecked(
                             new_size,
                             cur.align(),
                         )),
@@ -257,7 +257,9 @@ impl<'a, T> RawVec<'a, T> {
                     let new_cap = if elem_size > (!0) / 8 { 1 } else { 4 };
                     match self.a.alloc_array::<T>(new_cap) {
                         Ok(ptr) => (new_cap, ptr),
-                        Err(_) => handle_alloc_error(Layout::array::<T>(new_cap).unwrap()),
+                        Err(_) => self
+                            .a
+                            .handle_alloc_error(Layout::array::<T>(new_cap).unwrap()),
                     }
                 }
             };
@@ -537,11 +539,23 @@ impl<'a, T> RawVec<'a, T> {
                 let new_size = elem_size * amount;
                 let align = mem::align_of::<T>();
                 let old_layout = Layout::from_size_align_unchecked(old_size, align);
+
+                // If we are the most recent allocation, always give the excess
+                // capacity back to the arena, however little of it there is.
+                if let Some(p) =
+                    self.a
+                        .shrink_last_allocation_in_place(self.ptr.cast(), old_layout, new_size)
+                {
+                    self.ptr = p.cast();
+                    self.cap = amount;
+                    return;
+                }
+
                 match self.a.realloc(self.ptr.cast(), old_layout, new_size) {
                     Ok(p) => self.ptr = p.cast(),
-                    Err(_) => {
-                        handle_alloc_error(Layout::from_size_align_unchecked(new_size, align))
-                    }
+                    Err(_) => self
+                        .a
+                        .handle_alloc_error(Layout::from_size_align_unchecked(new_size, align)),
                 }
             }
             self.cap = amount;
@@ -684,7 +698,7 @@ impl<'a, T> RawVec<'a, T> {
             };
 
             if let (Err(AllocErr), Infallible) = (&res, fallibility) {
-                handle_alloc_error(new_layout);
+                self.a.handle_alloc_error(new_layout);
             }
 
             self.ptr = res?.cast();
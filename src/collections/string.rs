@@ -696,8 +696,29 @@ impl<'bump> String<'bump> {
         t
     }
 
+    /// Construct a new `String<'bump>` from a standard library `String`,
+    /// copying its contents into the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = String::from_string_in(std::string::String::from("hello"), &b);
+    /// assert_eq!(s, "hello");
+    /// ```
+    pub fn from_string_in(s: core_alloc::string::String, bump: &'bump Bump) -> String<'bump> {
+        String::from_str_in(&s, bump)
+    }
+
     /// Construct a new `String<'bump>` from an iterator of `char`s.
     ///
+    /// Space for the iterator's `size_hint` lower bound, at one byte per
+    /// `char`, is reserved up front, and each `char` is encoded as UTF-8
+    /// directly into the arena.
+    ///
     /// # Examples
     ///
     /// ```
@@ -710,9 +731,29 @@ impl<'bump> String<'bump> {
     /// ```
     pub fn from_iter_in<I: IntoIterator<Item = char>>(iter: I, bump: &'bump Bump) -> String<'bump> {
         let mut s = String::new_in(bump);
-        for c in iter {
-            s.push(c);
-        }
+        s.extend(iter);
+        s
+    }
+
+    /// Construct a new `String<'bump>` by concatenating an iterator of string
+    /// slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = String::from_str_iter_in("a-b-c".split('-'), &b);
+    /// assert_eq!(s, "abc");
+    /// ```
+    pub fn from_str_iter_in<'s, I: IntoIterator<Item = &'s str>>(
+        iter: I,
+        bump: &'bump Bump,
+    ) -> String<'bump> {
+        let mut s = String::new_in(bump);
+        s.extend(iter);
         s
     }
 
@@ -2151,6 +2192,37 @@ impl<'a, 'bump> DoubleEndedIterator for Drain<'a, 'bump> {
 
 impl<'a, 'bump> FusedIterator for Drain<'a, 'bump> {}
 
+/// Conversion of string slices into arena-allocated [`String`]s.
+///
+/// This is the arena counterpart of `ToString`, which can't be used since
+/// it has no way of being given a [`Bump`].
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::ToBumpString};
+///
+/// let b = Bump::new();
+///
+/// let s = "hello".to_bump_string_in(&b);
+/// assert_eq!(s, "hello");
+///
+/// let owned = std::string::String::from("world");
+/// let t = owned.to_bump_string_in(&b);
+/// assert_eq!(t, "world");
+/// ```
+pub trait ToBumpString {
+    /// Copy `self` into a new `String` allocated in `bump`.
+    fn to_bump_string_in<'bump>(&self, bump: &'bump Bump) -> String<'bump>;
+}
+
+impl ToBumpString for str {
+    #[inline]
+    fn to_bump_string_in<'bump>(&self, bump: &'bump Bump) -> String<'bump> {
+        String::from_str_in(self, bump)
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serialize {
     use super::*;
@@ -608,6 +608,33 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         v
     }
 
+    /// Construct a new `Vec` holding `n` clones of `elem`.
+    ///
+    /// This is the equivalent of `std`'s `vec![elem; n]`. Space for all `n`
+    /// elements is reserved up front, and every element, including the last,
+    /// is a clone of `elem`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let row = Vec::from_elem_in(0_u8, 4, &b);
+    /// assert_eq!(row, [0, 0, 0, 0]);
+    /// assert_eq!(row.capacity(), 4);
+    /// ```
+    pub fn from_elem_in(elem: T, n: usize, bump: &'bump Bump) -> Vec<'bump, T>
+    where
+        T: Clone,
+    {
+        let mut v = Vec::with_capacity_in(n, bump);
+        for _ in 0..n {
+            v.push(elem.clone());
+        }
+        v
+    }
+
     /// Creates a `Vec<'bump, T>` directly from the raw components of another vector.
     ///
     /// # Safety
@@ -821,6 +848,12 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// It will drop down as close as possible to the length but the allocator
     /// may still inform the vector that there is space for a few more elements.
     ///
+    /// Bump allocation can't free memory in the middle of the arena, so the
+    /// excess capacity is only given back to the arena if the vector's buffer
+    /// is the most recent allocation in its `Bump`. In that case, the elements
+    /// are moved up against the end of the buffer and the bump pointer is
+    /// rewound past the excess. Otherwise, the excess is simply left unused.
+    ///
     /// # Examples
     ///
     /// ```
@@ -840,6 +873,37 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Shrinks the capacity of the vector with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value. If the current capacity is less than the lower limit,
+    /// this is a no-op.
+    ///
+    /// As with [`shrink_to_fit`](Vec::shrink_to_fit), the excess capacity is
+    /// only given back to the arena if the vector's buffer is the most recent
+    /// allocation in its `Bump`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = Vec::with_capacity_in(10, &b);
+    /// vec.extend([1, 2, 3].iter().cloned());
+    /// assert_eq!(vec.capacity(), 10);
+    /// vec.shrink_to(4);
+    /// assert!(vec.capacity() >= 4);
+    /// vec.shrink_to(0);
+    /// assert!(vec.capacity() >= 3);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        if self.capacity() > min_capacity {
+            self.buf.shrink_to_fit(self.len.max(min_capacity));
+        }
+    }
+
     /// Converts the vector into `&'bump [T]`.
     ///
     /// # Examples
@@ -887,12 +951,45 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         unsafe { slice::from_raw_parts_mut(ptr, len) }
     }
 
+    /// Consumes and leaks the vector, returning a mutable reference to its
+    /// contents that lives as long as the arena.
+    ///
+    /// This is the same as [`into_bump_slice_mut`], under the name used by
+    /// `std`'s [`Vec::leak`]. The elements are never dropped, and any excess
+    /// capacity stays allocated in the arena until it is reset. Since the
+    /// arena never moves its allocations, the returned slice stays where the
+    /// vector's buffer was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let mut v = Vec::with_capacity_in(3, &b);
+    /// v.extend([1, 2, 3]);
+    /// let ptr = v.as_ptr();
+    ///
+    /// let slice: &mut [i32] = v.leak();
+    /// slice[0] = 10;
+    /// assert_eq!(slice, [10, 2, 3]);
+    /// assert_eq!(slice.as_ptr(), ptr);
+    /// ```
+    ///
+    /// [`into_bump_slice_mut`]: Vec::into_bump_slice_mut
+    /// [`Vec::leak`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.leak
+    pub fn leak(self) -> &'bump mut [T] {
+        self.into_bump_slice_mut()
+    }
+
     /// Shortens the vector, keeping the first `len` elements and dropping
     /// the rest.
     ///
     /// If `len` is greater than the vector's current length, this has no
     /// effect.
     ///
+    /// The removed elements are dropped in order.
+    ///
     /// The [`drain`] method can emulate `truncate`, but causes the excess
     /// elements to be returned instead of dropped.
     ///
@@ -942,21 +1039,16 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// [`clear`]: #method.clear
     /// [`drain`]: #method.drain
     pub fn truncate(&mut self, len: usize) {
-        let current_len = self.len;
+        if len >= self.len {
+            return;
+        }
         unsafe {
-            let mut ptr = self.as_mut_ptr().add(self.len);
-            // Set the final length at the end, keeping in mind that
-            // dropping an element might panic. Works around a missed
-            // optimization, as seen in the following issue:
-            // https://github.com/rust-lang/rust/issues/51802
-            let mut local_len = SetLenOnDrop::new(&mut self.len);
-
-            // drop any extra elements
-            for _ in len..current_len {
-                local_len.decrement_len(1);
-                ptr = ptr.offset(-1);
-                ptr::drop_in_place(ptr);
-            }
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), self.len - len);
+            // Set the length first, so that if dropping an element panics the
+            // rest of the tail is still dropped (by `drop_in_place`) and none
+            // of it is dropped again by the vector.
+            self.len = len;
+            ptr::drop_in_place(tail);
         }
     }
 
@@ -1007,6 +1099,13 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// Modifying the vector may cause its buffer to be reallocated,
     /// which would also make any pointers to it invalid.
     ///
+    /// The buffer lives in the vector's `Bump`, which never moves an
+    /// allocation, so otherwise the pointer is stable: it only changes when the
+    /// vector reallocates (for example by growing past its capacity, or by
+    /// [`shrink_to_fit`]). Leaking the vector with [`leak`] or
+    /// [`into_bump_slice`] keeps the elements at this address for the
+    /// lifetime of the arena.
+    ///
     /// The caller must also ensure that the memory the pointer (non-transitively) points to
     /// is never written to (except inside an `UnsafeCell`) using this pointer or any pointer
     /// derived from it. If you need to mutate the contents of the slice, use [`as_mut_ptr`].
@@ -1029,6 +1128,9 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// ```
     ///
     /// [`as_mut_ptr`]: Vec::as_mut_ptr
+    /// [`shrink_to_fit`]: Vec::shrink_to_fit
+    /// [`leak`]: Vec::leak
+    /// [`into_bump_slice`]: Vec::into_bump_slice
     #[inline]
     pub fn as_ptr(&self) -> *const T {
         // We shadow the slice method of the same name to avoid going through
@@ -1050,6 +1152,9 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// Modifying the vector may cause its buffer to be reallocated,
     /// which would also make any pointers to it invalid.
     ///
+    /// As with [`as_ptr`], the pointer is otherwise stable, because the arena
+    /// never moves the vector's buffer.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1071,6 +1176,8 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// }
     /// assert_eq!(&*x, &[0, 1, 2, 3]);
     /// ```
+    ///
+    /// [`as_ptr`]: Vec::as_ptr
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut T {
         // We shadow the slice method of the same name to avoid going through
@@ -1344,6 +1451,58 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Creates an iterator that removes the elements in the vector for which
+    /// the predicate returns `true` and yields them, in order.
+    ///
+    /// The elements for which the predicate returns `false` stay in the
+    /// vector, in their original order, and are compacted towards its front
+    /// in the same pass.
+    ///
+    /// Unlike [`drain_filter`](Vec::drain_filter), dropping the iterator
+    /// before it is exhausted stops the filtering: the elements that were not
+    /// visited yet are kept, and the predicate is not called on them. If the
+    /// predicate panics, the element it panicked on and all elements after it
+    /// are kept too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    /// use bumpalo::collections::{CollectIn, Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut numbers = bumpalo::vec![in &b; 1, 2, 3, 4, 5, 6];
+    ///
+    /// let evens: Vec<_> = numbers.extract_if(|x| *x % 2 == 0).collect_in(&b);
+    /// assert_eq!(numbers, &[1, 3, 5]);
+    /// assert_eq!(evens, &[2, 4, 6]);
+    ///
+    /// // Only the elements visited before the iterator is dropped are removed.
+    /// let mut numbers = bumpalo::vec![in &b; 1, 2, 3, 4, 5, 6];
+    /// assert_eq!(numbers.extract_if(|x| *x % 2 == 0).next(), Some(2));
+    /// assert_eq!(numbers, &[1, 3, 4, 5, 6]);
+    /// ```
+    pub fn extract_if<'a, F>(&'a mut self, filter: F) -> ExtractIf<'a, 'bump, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len();
+
+        // Guard against us getting leaked (leak amplification)
+        unsafe {
+            self.set_len(0);
+        }
+
+        ExtractIf {
+            vec: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred: filter,
+        }
+    }
+
     /// Removes all but the first of consecutive elements in the vector that resolve to the same
     /// key.
     ///
@@ -1578,6 +1737,8 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
 
     /// Clears the vector, removing all values.
     ///
+    /// The values are dropped in order, from first to last.
+    ///
     /// Note that this method has no effect on the allocated capacity
     /// of the vector.
     ///
@@ -1973,11 +2134,6 @@ impl<'a> SetLenOnDrop<'a> {
     fn increment_len(&mut self, increment: usize) {
         self.local_len += increment;
     }
-
-    #[inline]
-    fn decrement_len(&mut self, decrement: usize) {
-        self.local_len -= decrement;
-    }
 }
 
 impl<'a> Drop for SetLenOnDrop<'a> {
@@ -2243,6 +2399,12 @@ macro_rules! __impl_slice_eq1 {
 __impl_slice_eq1! { Vec<'a, A>, Vec<'b, B> }
 __impl_slice_eq1! { Vec<'a, A>, &'b [B] }
 __impl_slice_eq1! { Vec<'a, A>, &'b mut [B] }
+__impl_slice_eq1! { Vec<'a, A>, [B] }
+__impl_slice_eq1! { Vec<'a, A>, core_alloc::vec::Vec<B> }
+__impl_slice_eq1! { &'a [A], Vec<'b, B> }
+__impl_slice_eq1! { &'a mut [A], Vec<'b, B> }
+__impl_slice_eq1! { [A], Vec<'b, B> }
+__impl_slice_eq1! { core_alloc::vec::Vec<A>, Vec<'b, B> }
 // __impl_slice_eq1! { Cow<'a, [A]>, Vec<'b, B>, Clone }
 
 macro_rules! __impl_slice_eq1_array {
@@ -2262,6 +2424,7 @@ macro_rules! __impl_slice_eq1_array {
 __impl_slice_eq1_array! { Vec<'a, A>, [B; N] }
 __impl_slice_eq1_array! { Vec<'a, A>, &'b [B; N] }
 __impl_slice_eq1_array! { Vec<'a, A>, &'b mut [B; N] }
+__impl_slice_eq1_array! { [A; N], Vec<'b, B> }
 
 /// Implements comparison of vectors, lexicographically.
 impl<'bump, T: 'bump + PartialOrd> PartialOrd for Vec<'bump, T> {
@@ -2741,6 +2904,75 @@ where
     }
 }
 
+/// An iterator produced by calling [`Vec::extract_if`].
+#[derive(Debug)]
+pub struct ExtractIf<'a, 'bump: 'a, T: 'a + 'bump, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    vec: &'a mut Vec<'bump, T>,
+    idx: usize,
+    del: usize,
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, 'bump, T, F> Iterator for ExtractIf<'a, 'bump, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.old_len {
+                let i = self.idx;
+                let v = slice::from_raw_parts_mut(self.vec.as_mut_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                // Only advance once the predicate has returned, so that a
+                // panicking predicate leaves `v[i]` to be kept by `drop`.
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let del = self.del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - del];
+                    // This is safe because self.vec has length 0
+                    // thus its elements will not have Drop::drop
+                    // called on them in the event of a panic.
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<'a, 'bump, T, F> Drop for ExtractIf<'a, 'bump, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        unsafe {
+            // Keep the elements that were not visited, moving them down over
+            // the gap left by the extracted ones.
+            if self.idx < self.old_len && self.del > 0 {
+                let ptr = self.vec.as_mut_ptr();
+                let src = ptr.add(self.idx);
+                let dst = src.sub(self.del);
+                ptr::copy(src, dst, self.old_len - self.idx);
+            }
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'bump> io::Write for Vec<'bump, u8> {
     #[inline]
@@ -11,10 +11,16 @@ pub extern crate alloc as core_alloc;
 pub mod boxed;
 #[cfg(feature = "collections")]
 pub mod collections;
+#[cfg(feature = "rc")]
+pub mod rc;
+#[cfg(feature = "std")]
+mod sharded;
 
 mod alloc;
+mod nested;
 
-use core::cell::Cell;
+use core::any::TypeId;
+use core::cell::{Cell, OnceCell};
 use core::cmp::Ordering;
 use core::fmt::Display;
 use core::iter;
@@ -24,6 +30,8 @@ use core::ptr::{self, NonNull};
 use core::slice;
 use core::str;
 use core_alloc::alloc::{alloc, dealloc, Layout};
+#[cfg(feature = "tracking")]
+use core_alloc::collections::BTreeMap;
 
 #[cfg(feature = "allocator_api")]
 use core_alloc::alloc::{AllocError, Allocator};
@@ -32,6 +40,9 @@ use core_alloc::alloc::{AllocError, Allocator};
 use allocator_api2::alloc::{AllocError, Allocator};
 
 pub use alloc::AllocErr;
+pub use nested::NestedBump;
+#[cfg(feature = "std")]
+pub use sharded::ShardedBump;
 
 /// An error returned from [`Bump::try_alloc_try_with`].
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -288,11 +299,273 @@ impl<E: Display> Display for AllocOrInitError<E> {
 /// Because of backwards compatibility, allocations that fail
 /// due to allocation limits will not present differently than
 /// errors due to resource exhaustion.
-#[derive(Debug)]
+///
+/// ### Custom Chunk Sources
+///
+/// By default, a `Bump` gets the chunks of memory that it bump allocates into
+/// from the global allocator. A `Bump` created with
+/// [`with_chunk_source`][Bump::with_chunk_source] instead requests its chunks
+/// from the given [`ChunkSource`], and hands them back to it when they are
+/// freed by [`reset`][Bump::reset] or by dropping the `Bump`. This lets, for
+/// example, a pool of chunks be recycled across many arenas.
 pub struct Bump<const MIN_ALIGN: usize = 1> {
     // The current chunk we are bump allocating within.
     current_chunk_footer: Cell<NonNull<ChunkFooter>>,
     allocation_limit: Cell<Option<usize>>,
+    // Rarely changed settings, only allocated once one of them is set, so
+    // that they don't make every `Bump` bigger.
+    config: OnceCell<core_alloc::boxed::Box<ArenaConfig>>,
+    // The high-water mark of `allocated_bytes`. Since `allocated_bytes` only
+    // grows when a new chunk is allocated, this is only updated then, off of
+    // the fast path.
+    #[cfg(feature = "tracking")]
+    peak_allocated_bytes: Cell<usize>,
+    // The most recent allocation made with `alloc_tagged`, which links back
+    // to the one before it, and so on.
+    last_tagged: Cell<Option<NonNull<TagHeader>>>,
+    // The most recent allocation made with `alloc_with_drop`, which links
+    // back to the one before it, and so on.
+    last_drop: Cell<Option<NonNull<DropHeader>>>,
+    // The chunk that was allocated when the arena was empty, which is what
+    // `reset_keeping_first` keeps. This is not necessarily the last chunk in
+    // the `prev` list, because chunks for over-aligned allocations are linked
+    // in behind the current chunk.
+    first_chunk_footer: Cell<NonNull<ChunkFooter>>,
+    // Replaced with a fresh value whenever allocations are freed in bulk, so
+    // that `AllocHandle`s can tell whether they still point at live values.
+    generation: Cell<u64>,
+    // The most recent canary record and the number of allocations made so
+    // far, or `None` if this arena was not created with `with_canaries`.
+    #[cfg(feature = "canaries")]
+    canaries: Cell<Option<CanaryState>>,
+    // The strings interned by `alloc_str_interned`, or `None` if this arena
+    // was not created with `with_interner`.
+    #[cfg(feature = "std")]
+    interner: core::cell::RefCell<Option<Interner>>,
+    // Bytes allocated so far with each alignment, indexed by the alignment's
+    // base-two logarithm.
+    #[cfg(feature = "tracking")]
+    alignment_histogram: [Cell<usize>; usize::BITS as usize],
+}
+
+// The settings of a `Bump` that most arenas leave at their defaults.
+struct ArenaConfig {
+    // Where chunks come from and go back to, or the global allocator if
+    // `None`.
+    chunk_source: Option<core_alloc::boxed::Box<dyn ChunkSource + Send>>,
+    // The smallest size (without footer) that we will initially try to
+    // allocate a new chunk with.
+    min_chunk_size: Cell<usize>,
+    // Called instead of panicking when an infallible allocation fails.
+    oom_handler: Cell<Option<fn() -> !>>,
+    // Allocations at least this big that don't fit in the current chunk get a
+    // chunk of their own, linked in behind the current one.
+    large_allocation_threshold: Cell<Option<usize>>,
+    // Each new chunk is this `(numerator, denominator)` fraction of the size
+    // of the previous one.
+    chunk_growth_factor: Cell<(u32, u32)>,
+}
+
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        ArenaConfig {
+            chunk_source: None,
+            min_chunk_size: Cell::new(0),
+            oom_handler: Cell::new(None),
+            large_allocation_threshold: Cell::new(None),
+            chunk_growth_factor: Cell::new((2, 1)),
+        }
+    }
+}
+
+/// A source of the chunks of memory that a [`Bump`] bump allocates into.
+///
+/// See [`Bump::with_chunk_source`] for details.
+///
+/// A blanket implementation for `&S` means that a single, shared source (for
+/// example a `&'static` pool) can be handed to many arenas.
+///
+/// ## Safety
+///
+/// Implementations must uphold the same contract as a global allocator:
+/// `alloc_chunk` must return either `None` or a pointer to memory that is
+/// valid for reads and writes of `layout.size()` bytes, aligned to
+/// `layout.align()`, and not used by anything else until it is passed back to
+/// `free_chunk`.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{Bump, ChunkSource};
+/// use std::alloc::{alloc, dealloc, Layout};
+/// use std::ptr::NonNull;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct CountingSource(AtomicUsize);
+///
+/// unsafe impl ChunkSource for CountingSource {
+///     fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         NonNull::new(unsafe { alloc(layout) })
+///     }
+///
+///     unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+///         self.0.fetch_sub(1, Ordering::Relaxed);
+///         dealloc(ptr.as_ptr(), layout);
+///     }
+/// }
+///
+/// static SOURCE: CountingSource = CountingSource(AtomicUsize::new(0));
+///
+/// let bump = Bump::with_chunk_source(&SOURCE);
+/// bump.alloc(42);
+/// assert_eq!(SOURCE.0.load(Ordering::Relaxed), 1);
+///
+/// drop(bump);
+/// assert_eq!(SOURCE.0.load(Ordering::Relaxed), 0);
+/// ```
+pub unsafe trait ChunkSource {
+    /// Allocate a chunk of memory with the given layout, or return `None` on
+    /// failure.
+    fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Give back a chunk of memory that was allocated by `alloc_chunk`.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must have been returned by `self.alloc_chunk(layout)` with the
+    /// same `layout`, and not already freed.
+    unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+unsafe impl<S: ChunkSource + ?Sized> ChunkSource for &S {
+    #[inline]
+    fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).alloc_chunk(layout)
+    }
+
+    #[inline]
+    unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).free_chunk(ptr, layout)
+    }
+}
+
+/// A source of elements that [`Bump::alloc_slice`] can place into an arena.
+///
+/// This trait is sealed and implemented for:
+///
+/// * `&[T]`, `&mut [T]` and `&[T; N]` where `T: Clone`, which clone each
+///   element like [`alloc_slice_clone`](Bump::alloc_slice_clone),
+/// * `[T; N]` and `Vec<T>`, which move their elements into the arena.
+///
+/// Arbitrary iterators are not covered, since a blanket implementation for
+/// them would overlap with the slice implementations. Use
+/// [`alloc_slice_fill_iter`](Bump::alloc_slice_fill_iter) for those.
+pub trait IntoArenaSlice<T>: into_arena_slice::Sealed {
+    #[doc(hidden)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr>;
+}
+
+mod into_arena_slice {
+    pub trait Sealed {}
+
+    impl<T> Sealed for &[T] {}
+    impl<T> Sealed for &mut [T] {}
+    impl<T, const N: usize> Sealed for &[T; N] {}
+    impl<T, const N: usize> Sealed for [T; N] {}
+    impl<T> Sealed for core_alloc::vec::Vec<T> {}
+}
+
+impl<T: Clone> IntoArenaSlice<T> for &[T] {
+    #[inline(always)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr> {
+        bump.try_alloc_slice_clone(self)
+    }
+}
+
+impl<T: Clone> IntoArenaSlice<T> for &mut [T] {
+    #[inline(always)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr> {
+        bump.try_alloc_slice_clone(self)
+    }
+}
+
+impl<T: Clone, const N: usize> IntoArenaSlice<T> for &[T; N] {
+    #[inline(always)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr> {
+        bump.try_alloc_slice_clone(self)
+    }
+}
+
+impl<T, const N: usize> IntoArenaSlice<T> for [T; N] {
+    #[inline(always)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr> {
+        bump.try_alloc_slice_fill_iter(self)
+    }
+}
+
+impl<T> IntoArenaSlice<T> for core_alloc::vec::Vec<T> {
+    #[inline(always)]
+    fn try_alloc_in<const MIN_ALIGN: usize>(
+        self,
+        bump: &Bump<MIN_ALIGN>,
+    ) -> Result<&mut [T], AllocErr> {
+        bump.try_alloc_slice_fill_iter(self)
+    }
+}
+
+/// An unsized value that [`Bump::alloc_unsized`] can copy into an arena.
+///
+/// This trait is sealed and implemented for `str` and for `[T]` where
+/// `T: Copy`, whose contents can be copied byte for byte.
+pub trait CopyUnsized: copy_unsized::Sealed {
+    #[doc(hidden)]
+    fn try_copy_in<'a, const MIN_ALIGN: usize>(
+        &self,
+        bump: &'a Bump<MIN_ALIGN>,
+    ) -> Result<&'a mut Self, AllocErr>;
+}
+
+mod copy_unsized {
+    pub trait Sealed {}
+
+    impl Sealed for str {}
+    impl<T: Copy> Sealed for [T] {}
+}
+
+impl CopyUnsized for str {
+    #[inline(always)]
+    fn try_copy_in<'a, const MIN_ALIGN: usize>(
+        &self,
+        bump: &'a Bump<MIN_ALIGN>,
+    ) -> Result<&'a mut str, AllocErr> {
+        bump.try_alloc_str(self)
+    }
+}
+
+impl<T: Copy> CopyUnsized for [T] {
+    #[inline(always)]
+    fn try_copy_in<'a, const MIN_ALIGN: usize>(
+        &self,
+        bump: &'a Bump<MIN_ALIGN>,
+    ) -> Result<&'a mut [T], AllocErr> {
+        bump.try_alloc_slice_copy(self)
+    }
 }
 
 #[repr(C)]
@@ -326,7 +599,11 @@ struct ChunkFooter {
 /// For the canonical empty chunk to be `static`, its type must be `Sync`, which
 /// is the purpose of this wrapper type. This is safe because the empty chunk is
 /// immutable and never actually modified.
-#[repr(transparent)]
+///
+/// The wrapper is also over-aligned to `CHUNK_ALIGN` (the largest supported
+/// `MIN_ALIGN`) so that the empty chunk's bump pointer satisfies every arena's
+/// minimum alignment invariant, even before its first chunk is allocated.
+#[repr(C, align(16))]
 struct EmptyChunkFooter(ChunkFooter);
 
 unsafe impl Sync for EmptyChunkFooter {}
@@ -377,6 +654,19 @@ impl ChunkFooter {
     }
 }
 
+impl<const MIN_ALIGN: usize> core::fmt::Debug for Bump<MIN_ALIGN> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Walks the chunk list, so this is O(chunks), but never touches the
+        // allocated data itself.
+        f.debug_struct("Bump")
+            .field("chunk_count", &self.chunk_footers().count())
+            .field("allocated_bytes", &self.allocated_bytes())
+            .field("chunk_capacity", &self.chunk_capacity())
+            .field("allocation_limit", &self.allocation_limit())
+            .finish()
+    }
+}
+
 impl<const MIN_ALIGN: usize> Default for Bump<MIN_ALIGN> {
     fn default() -> Self {
         Self::with_min_align()
@@ -385,18 +675,37 @@ impl<const MIN_ALIGN: usize> Default for Bump<MIN_ALIGN> {
 
 impl<const MIN_ALIGN: usize> Drop for Bump<MIN_ALIGN> {
     fn drop(&mut self) {
+        let this = &*self;
+        // Free the chunks even if a destructor panics.
+        let _dealloc = Finally(|| {
+            // Don't turn a panic that is already unwinding into an abort.
+            #[cfg(feature = "canaries")]
+            if !panicking() {
+                this.check_canaries();
+            }
+            unsafe {
+                dealloc_chunk_list(this.current_chunk_footer.get(), this.chunk_source());
+            }
+        });
         unsafe {
-            dealloc_chunk_list(self.current_chunk_footer.get());
+            this.run_drops_until(None);
         }
     }
 }
 
 #[inline]
-unsafe fn dealloc_chunk_list(mut footer: NonNull<ChunkFooter>) {
+unsafe fn dealloc_chunk_list(
+    mut footer: NonNull<ChunkFooter>,
+    chunk_source: Option<&dyn ChunkSource>,
+) {
     while !footer.as_ref().is_empty() {
         let f = footer;
         footer = f.as_ref().prev.get();
-        dealloc(f.as_ref().data.as_ptr(), f.as_ref().layout);
+        let (data, layout) = (f.as_ref().data, f.as_ref().layout);
+        match chunk_source {
+            Some(source) => source.free_chunk(data, layout),
+            None => dealloc(data.as_ptr(), layout),
+        }
     }
 }
 
@@ -462,6 +771,106 @@ pub(crate) unsafe fn round_mut_ptr_up_to_unchecked(ptr: *mut u8, divisor: usize)
     ptr.add(delta)
 }
 
+/// A non-null pointer with the given alignment that is not backed by any
+/// allocation, for zero-sized allocations.
+#[inline]
+fn dangling_aligned(align: usize) -> NonNull<u8> {
+    debug_assert!(align.is_power_of_two());
+    // Offset from null rather than casting `align` to a pointer, so that the
+    // pointer has no provenance instead of an exposed one.
+    unsafe { NonNull::new_unchecked(ptr::null_mut::<u8>().wrapping_add(align)) }
+}
+
+/// Fill `len` elements starting at `dst` with copies of `value`, back to back.
+///
+/// Rather than copying `value` once per repetition, this copies `value` once
+/// and then repeatedly doubles the initialized prefix, like `slice::repeat`.
+///
+/// `len` must be a multiple of `value.len()` (or zero), and `dst` must be
+/// valid for writes of `len` elements.
+#[inline]
+unsafe fn write_repeated<T: Copy>(dst: *mut T, value: &[T], len: usize) {
+    if len == 0 {
+        return;
+    }
+    debug_assert_eq!(len % value.len(), 0);
+
+    ptr::copy_nonoverlapping(value.as_ptr(), dst, value.len());
+    let mut filled = value.len();
+    while filled < len {
+        let n = filled.min(len - filled);
+        ptr::copy_nonoverlapping(dst, dst.add(filled), n);
+        filled += n;
+    }
+}
+
+/// Drops the first `len` elements starting at `start` when dropped, unless
+/// forgotten. Used to clean up partially initialized slices.
+struct DropInitialized<T> {
+    start: *mut T,
+    len: usize,
+}
+
+impl<T> Drop for DropInitialized<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.start, self.len));
+        }
+    }
+}
+
+/// Runs the closure when dropped, including while unwinding from a panic.
+struct Finally<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for Finally<F> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+/// Initialize every element of `dst` with `f(index)`, in order.
+///
+/// If `f` panics, the elements initialized so far are dropped before the panic
+/// propagates, so that none of them are leaked.
+#[inline(always)]
+fn fill_uninit_with<T, F>(dst: &mut [mem::MaybeUninit<T>], mut f: F) -> &mut [T]
+where
+    F: FnMut(usize) -> T,
+{
+    // Write everything through this one pointer, without reborrowing `dst`,
+    // so that the guard's pointer stays valid until it's forgotten.
+    let len = dst.len();
+    let start = dst.as_mut_ptr().cast::<T>();
+    let mut guard = DropInitialized { start, len: 0 };
+    while guard.len < len {
+        unsafe { start.add(guard.len).write(f(guard.len)) };
+        guard.len += 1;
+    }
+    mem::forget(guard);
+
+    unsafe { slice::from_raw_parts_mut(start, len) }
+}
+
+/// In debug builds, assert that a freshly allocated `dst` does not overlap the
+/// `src` slice that is about to be copied or cloned into it.
+///
+/// This can't happen with safe code, but it can when unsafe code keeps reading
+/// from arena memory that has been reset and is being reused.
+#[inline(always)]
+fn debug_assert_nonoverlapping<T>(src: &[T], dst: NonNull<T>) {
+    if cfg!(debug_assertions) {
+        let size = mem::size_of_val(src);
+        let src_start = src.as_ptr() as usize;
+        let dst_start = dst.as_ptr() as usize;
+        assert!(
+            size == 0 || src_start + size <= dst_start || dst_start + size <= src_start,
+            "source slice at {src_start:#x} overlaps its destination allocation at \
+             {dst_start:#x} (both {size} bytes); was the source in arena memory \
+             that has since been reset?"
+        );
+    }
+}
+
 // The typical page size these days.
 //
 // Note that we don't need to exactly match page size for correctness, and it is
@@ -470,6 +879,11 @@ pub(crate) unsafe fn round_mut_ptr_up_to_unchecked(ptr: *mut u8, divisor: usize)
 // looking up the actual page size implies.
 const TYPICAL_PAGE_SIZE: usize = 0x1000;
 
+// Allocations whose alignment is larger than this get a chunk of their own
+// when they don't fit in the current chunk, rather than becoming the start of
+// a new current chunk. See `Bump::alloc_layout_in_dedicated_chunk`.
+const MAX_SHARED_CHUNK_ALIGN: usize = TYPICAL_PAGE_SIZE;
+
 // We only support alignments of up to 16 bytes for iter_allocated_chunks.
 const SUPPORTED_ITER_ALIGNMENT: usize = 16;
 const CHUNK_ALIGN: usize = SUPPORTED_ITER_ALIGNMENT;
@@ -534,6 +948,9 @@ fn allocation_size_overflow<T>() -> T {
 impl Bump<1> {
     /// Construct a new arena to bump allocate into.
     ///
+    /// To construct an arena with a minimum alignment other than `1`, use
+    /// [`Bump::with_min_align`] (or `Bump::<N>::default()`) instead.
+    ///
     /// ## Example
     ///
     /// ```
@@ -556,6 +973,68 @@ impl Bump<1> {
         Bump::try_with_capacity(0)
     }
 
+    /// Construct a new arena that checks for buffer overruns.
+    ///
+    /// Every non-zero-sized allocation made through
+    /// [`alloc_layout`][Bump::alloc_layout] (and so through all the other
+    /// allocation methods) is followed by a few canary bytes. The canaries are
+    /// validated when the arena is [reset][Bump::reset] or dropped, and can be
+    /// validated at any time with [`check_canaries`][Bump::check_canaries].
+    ///
+    /// This is a testing aid and makes every allocation bigger and slower. The
+    /// canaries, and the bookkeeping used to find them again, are stored in
+    /// the arena itself, so they count toward
+    /// [`allocated_bytes`][Bump::allocated_bytes].
+    ///
+    /// Requires the `canaries` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_canaries();
+    /// let buf = bump.alloc_slice_fill_copy(4, 0_u8);
+    /// buf[3] = 1;
+    /// bump.check_canaries();
+    /// ```
+    #[cfg(feature = "canaries")]
+    pub fn with_canaries() -> Self {
+        let bump = Bump::new();
+        bump.canaries.set(Some(CanaryState {
+            last: None,
+            count: 0,
+        }));
+        bump
+    }
+
+    /// Construct a new arena that interns the strings allocated with
+    /// [`alloc_str_interned`][Bump::alloc_str_interned].
+    ///
+    /// Interning a string that is equal to one interned before returns the
+    /// earlier copy instead of allocating a new one, so repeated strings (such
+    /// as identifiers in a tokenizer) take up arena space only once, and
+    /// interned strings can be compared by pointer.
+    ///
+    /// The table of interned strings lives on the heap, not in the arena, and
+    /// forgets strings when they are freed by [`reset`][Bump::reset] or at the
+    /// end of a [`with_checkpoint`][Bump::with_checkpoint] scope.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_interner();
+    /// let a = bump.alloc_str_interned("ident");
+    /// let b = bump.alloc_str_interned(&String::from("ident"));
+    /// assert!(std::ptr::eq(a, b));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_interner() -> Self {
+        let bump = Bump::new();
+        *bump.interner.borrow_mut() = Some(Interner::default());
+        bump
+    }
+
     /// Construct a new arena with the specified byte capacity to bump allocate
     /// into.
     ///
@@ -590,11 +1069,76 @@ impl Bump<1> {
     pub fn try_with_capacity(capacity: usize) -> Result<Self, AllocErr> {
         Self::try_with_min_align_and_capacity(capacity)
     }
+
+    /// Construct a new arena that gets the chunks of memory it bump allocates
+    /// into from `source`, instead of from the global allocator.
+    ///
+    /// Chunks are handed back to `source` when [`reset`][Bump::reset] frees
+    /// them and when the arena is dropped. No chunk is requested until the
+    /// first allocation is made.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, ChunkSource};
+    /// use std::alloc::{alloc, dealloc, Layout};
+    /// use std::ptr::NonNull;
+    ///
+    /// struct Global;
+    ///
+    /// unsafe impl ChunkSource for Global {
+    ///     fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+    ///         NonNull::new(unsafe { alloc(layout) })
+    ///     }
+    ///
+    ///     unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+    ///         dealloc(ptr.as_ptr(), layout)
+    ///     }
+    /// }
+    ///
+    /// let bump = Bump::with_chunk_source(Global);
+    /// assert_eq!(*bump.alloc(5), 5);
+    /// ```
+    pub fn with_chunk_source<S>(source: S) -> Self
+    where
+        S: ChunkSource + Send + 'static,
+    {
+        Self::with_min_align_and_chunk_source(source)
+    }
+
+    /// Construct a new arena that gets the chunks of memory it bump allocates
+    /// into from `parent`, instead of from the global allocator.
+    ///
+    /// Resetting or dropping `parent` reclaims everything that the nested
+    /// arena allocated, so a tree of arenas can be wiped by resetting its
+    /// root. The returned [`NestedBump`] borrows `parent`, which ensures that
+    /// the nested arena does not outlive the parent's next reset.
+    ///
+    /// No chunk is requested from `parent` until the first allocation is made.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    ///
+    /// let parent = Bump::new();
+    /// let child = Bump::new_in(&parent);
+    /// assert_eq!(*child.alloc(5), 5);
+    /// assert!(parent.allocated_bytes_including_metadata() > 0);
+    /// ```
+    pub fn new_in<const PARENT_MIN_ALIGN: usize>(
+        parent: &Bump<PARENT_MIN_ALIGN>,
+    ) -> NestedBump<'_> {
+        Bump::with_min_align_in(parent)
+    }
 }
 
 impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// Create a new `Bump` that enforces a minimum alignment.
     ///
+    /// This is the minimum-alignment counterpart of [`Bump::new`]: no memory is
+    /// allocated until the first allocation is made.
+    ///
     /// The minimum alignment must be a power of two and no larger than `16`.
     ///
     /// Enforcing a minimum alignment can speed up allocation of objects with
@@ -635,6 +1179,19 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         Bump {
             current_chunk_footer: Cell::new(EMPTY_CHUNK.get()),
             allocation_limit: Cell::new(None),
+            config: OnceCell::new(),
+            #[cfg(feature = "tracking")]
+            peak_allocated_bytes: Cell::new(0),
+            last_tagged: Cell::new(None),
+            last_drop: Cell::new(None),
+            first_chunk_footer: Cell::new(EMPTY_CHUNK.get()),
+            generation: Cell::new(fresh_generation()),
+            #[cfg(feature = "canaries")]
+            canaries: Cell::new(None),
+            #[cfg(feature = "std")]
+            interner: core::cell::RefCell::new(None),
+            #[cfg(feature = "tracking")]
+            alignment_histogram: core::array::from_fn(|_| Cell::new(0)),
         }
     }
 
@@ -719,6 +1276,19 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
             return Ok(Bump {
                 current_chunk_footer: Cell::new(EMPTY_CHUNK.get()),
                 allocation_limit: Cell::new(None),
+                config: OnceCell::new(),
+                #[cfg(feature = "tracking")]
+                peak_allocated_bytes: Cell::new(0),
+                last_tagged: Cell::new(None),
+                last_drop: Cell::new(None),
+                first_chunk_footer: Cell::new(EMPTY_CHUNK.get()),
+                generation: Cell::new(fresh_generation()),
+                #[cfg(feature = "canaries")]
+                canaries: Cell::new(None),
+                #[cfg(feature = "std")]
+                interner: core::cell::RefCell::new(None),
+                #[cfg(feature = "tracking")]
+                alignment_histogram: core::array::from_fn(|_| Cell::new(0)),
             });
         }
 
@@ -729,6 +1299,7 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                 Self::new_chunk_memory_details(None, layout).ok_or(AllocErr)?,
                 layout,
                 EMPTY_CHUNK.get(),
+                None,
             )
             .ok_or(AllocErr)?
         };
@@ -736,9 +1307,168 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         Ok(Bump {
             current_chunk_footer: Cell::new(chunk_footer),
             allocation_limit: Cell::new(None),
+            config: OnceCell::new(),
+            #[cfg(feature = "tracking")]
+            peak_allocated_bytes: Cell::new(unsafe { chunk_footer.as_ref().allocated_bytes }),
+            last_tagged: Cell::new(None),
+            last_drop: Cell::new(None),
+            first_chunk_footer: Cell::new(chunk_footer),
+            generation: Cell::new(fresh_generation()),
+            #[cfg(feature = "canaries")]
+            canaries: Cell::new(None),
+            #[cfg(feature = "std")]
+            interner: core::cell::RefCell::new(None),
+            #[cfg(feature = "tracking")]
+            alignment_histogram: core::array::from_fn(|_| Cell::new(0)),
         })
     }
 
+    /// Create a new `Bump` that enforces a minimum alignment and gets the
+    /// chunks of memory it bump allocates into from `source`.
+    ///
+    /// See [`with_min_align`][Bump::with_min_align] and
+    /// [`with_chunk_source`][Bump::with_chunk_source] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid minimum alignments.
+    pub fn with_min_align_and_chunk_source<S>(source: S) -> Self
+    where
+        S: ChunkSource + Send + 'static,
+    {
+        let bump = Self::with_min_align();
+        let config = ArenaConfig {
+            chunk_source: Some(core_alloc::boxed::Box::new(source)),
+            ..ArenaConfig::default()
+        };
+        if bump.config.set(core_alloc::boxed::Box::new(config)).is_err() {
+            unreachable!("a new arena has no config yet");
+        }
+        bump
+    }
+
+    /// Create a new `Bump` that enforces a minimum alignment and gets the
+    /// chunks of memory it bump allocates into from `parent`.
+    ///
+    /// See [`with_min_align`][Bump::with_min_align] and
+    /// [`new_in`][Bump::new_in] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid minimum alignments.
+    pub fn with_min_align_in<const PARENT_MIN_ALIGN: usize>(
+        parent: &Bump<PARENT_MIN_ALIGN>,
+    ) -> NestedBump<'_, MIN_ALIGN> {
+        NestedBump::new(parent)
+    }
+
+    /// Create a new, empty `Bump` whose initial chunk is as big as all of
+    /// `other`'s chunks put together.
+    ///
+    /// This warm-starts a fresh arena (for example, one being added to a pool)
+    /// to the size that a similar workload has grown `other` to, so that the
+    /// new arena doesn't need to grow through the same chunks again. Only the
+    /// capacity is taken from `other`: the new arena has no allocation limit,
+    /// minimum chunk size, or custom chunk source.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the initial capacity fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    ///
+    /// let old = Bump::new();
+    /// for i in 0..10_000 {
+    ///     old.alloc(i);
+    /// }
+    /// let total: usize = old.chunk_capacities().sum();
+    ///
+    /// let new = Bump::new_like(&old);
+    /// assert_eq!(new.chunk_capacities().count(), 1);
+    /// assert!(new.chunk_capacity() >= total);
+    /// ```
+    pub fn new_like(other: &Self) -> Self {
+        Self::try_new_like(other).unwrap_or_else(|_| oom())
+    }
+
+    /// Like [`new_like`](Bump::new_like), but returns an `Err` instead of
+    /// panicking.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the initial capacity fails.
+    pub fn try_new_like(other: &Self) -> Result<Self, AllocErr> {
+        Self::try_with_min_align_and_capacity(other.chunk_capacities().sum())
+    }
+
+    /// Compute the byte capacity to request when constructing an arena so
+    /// that `count` allocations of type `T` fit within its initial chunk.
+    ///
+    /// The returned value accounts for any padding introduced by `T`'s
+    /// alignment and this arena's minimum alignment. Pass it to
+    /// [`with_capacity`][Bump::with_capacity] (or
+    /// [`with_min_align_and_capacity`][Bump::with_min_align_and_capacity]
+    /// with the same `MIN_ALIGN`) to avoid allocating any further chunks.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the computed capacity overflows `usize`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    ///
+    /// let mut bump = Bump::with_capacity(Bump::<1>::capacity_for::<u64>(1000));
+    /// for i in 0..1000_u64 {
+    ///     bump.alloc(i);
+    /// }
+    /// assert_eq!(bump.iter_allocated_chunks().count(), 1);
+    /// ```
+    pub fn capacity_for<T>(count: usize) -> usize {
+        Self::layout_capacity(Layout::new::<T>(), count)
+    }
+
+    /// Compute the byte capacity to request when constructing an arena so
+    /// that `count` allocations of `layout` fit within its initial chunk.
+    ///
+    /// See [`capacity_for`][Bump::capacity_for] for details.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the computed capacity overflows `usize`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    /// use std::alloc::Layout;
+    ///
+    /// let layout = Layout::from_size_align(3, 2).unwrap();
+    /// assert_eq!(Bump::<1>::layout_capacity(layout, 10), 40);
+    /// assert_eq!(Bump::<8>::layout_capacity(layout, 10), 80);
+    /// ```
+    pub fn layout_capacity(layout: Layout, count: usize) -> usize {
+        let align = layout.align().max(MIN_ALIGN);
+
+        // Every allocation's size gets rounded up to the effective alignment
+        // so that the bump pointer stays aligned for the next allocation.
+        let per_allocation =
+            round_up_to(layout.size(), align).unwrap_or_else(allocation_size_overflow);
+
+        // Chunks are only guaranteed to end on a `CHUNK_ALIGN` boundary, so
+        // the first allocation of a more-aligned type may need padding.
+        let initial_padding = align.saturating_sub(CHUNK_ALIGN);
+
+        per_allocation
+            .checked_mul(count)
+            .and_then(|size| size.checked_add(initial_padding))
+            .unwrap_or_else(allocation_size_overflow)
+    }
+
     /// Get this bump arena's minimum alignment.
     ///
     /// All objects allocated in this arena get aligned to this value.
@@ -797,9 +1527,303 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         self.allocation_limit.set(limit);
     }
 
-    /// How much headroom an arena has before it hits its allocation
-    /// limit.
-    fn allocation_limit_remaining(&self) -> Option<usize> {
+    /// The minimum size in bytes of new chunks allocated by this arena.
+    ///
+    /// Defaults to `0`, meaning that new chunk sizes are entirely determined
+    /// by the usual doubling schedule.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    ///
+    /// assert_eq!(bump.min_chunk_size(), 0);
+    ///
+    /// bump.set_min_chunk_size(1 << 20);
+    ///
+    /// assert_eq!(bump.min_chunk_size(), 1 << 20);
+    /// ```
+    pub fn min_chunk_size(&self) -> usize {
+        self.config.get().map_or(0, |c| c.min_chunk_size.get())
+    }
+
+    /// Set the minimum size in bytes of new chunks allocated by this arena.
+    ///
+    /// Each new chunk is normally about twice as big as the previous one (see
+    /// [`set_chunk_growth_factor`](Bump::set_chunk_growth_factor)). With
+    /// a minimum chunk size set, a new chunk gets at least `bytes` of capacity
+    /// instead, while the doubling still applies once it grows past that
+    /// floor. Existing chunks, including the current one, are not affected.
+    ///
+    /// As with the doubling schedule, if a chunk of the preferred size cannot
+    /// be allocated (or would not fit under the
+    /// [allocation limit](Bump::set_allocation_limit)), successively smaller
+    /// chunks are tried.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_min_chunk_size(1 << 20);
+    ///
+    /// bump.alloc(1_u8);
+    /// assert!(bump.chunk_capacity() >= (1 << 20) - 1);
+    /// ```
+    pub fn set_min_chunk_size(&self, bytes: usize) {
+        self.config().min_chunk_size.set(bytes);
+    }
+
+    /// The factor by which each new chunk is bigger than the previous one, as
+    /// a `(numerator, denominator)` fraction.
+    ///
+    /// Defaults to `(2, 1)`, i.e. doubling.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.chunk_growth_factor(), (2, 1));
+    ///
+    /// bump.set_chunk_growth_factor(3, 2);
+    /// assert_eq!(bump.chunk_growth_factor(), (3, 2));
+    /// ```
+    pub fn chunk_growth_factor(&self) -> (u32, u32) {
+        self.config.get().map_or((2, 1), |c| c.chunk_growth_factor.get())
+    }
+
+    /// Set the factor by which each new chunk is bigger than the previous one,
+    /// as the fraction `numerator / denominator`.
+    ///
+    /// A new chunk is normally about twice as big as the previous one. A
+    /// smaller factor, such as `3 / 2`, wastes less memory in the last chunk
+    /// at the cost of allocating chunks more often. A new chunk is still
+    /// always big enough for the allocation that needs it, and at least the
+    /// [minimum chunk size](Bump::set_min_chunk_size). Chunks smaller than a
+    /// page are still rounded up to a power of two, so the factor mostly
+    /// matters once chunks get large. Existing chunks are not affected.
+    ///
+    /// ## Panics
+    ///
+    /// Panics unless `numerator > denominator > 0`, since chunks must grow.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(4096);
+    /// bump.set_chunk_growth_factor(3, 2);
+    ///
+    /// let first = bump.chunk_capacity();
+    /// bump.alloc_slice_fill_copy(first + 1, 0_u8);
+    /// let capacities: Vec<usize> = bump.chunk_capacities().collect();
+    /// assert!(capacities[0] < 2 * first);
+    /// ```
+    pub fn set_chunk_growth_factor(&self, numerator: u32, denominator: u32) {
+        assert!(
+            denominator > 0 && numerator > denominator,
+            "chunk growth factor must be greater than one"
+        );
+        self.config().chunk_growth_factor.set((numerator, denominator));
+    }
+
+    /// Get the size at or above which allocations that don't fit in the
+    /// current chunk get a chunk of their own.
+    ///
+    /// See [`set_large_allocation_threshold`](Bump::set_large_allocation_threshold).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.large_allocation_threshold(), None);
+    ///
+    /// bump.set_large_allocation_threshold(Some(4096));
+    /// assert_eq!(bump.large_allocation_threshold(), Some(4096));
+    /// ```
+    pub fn large_allocation_threshold(&self) -> Option<usize> {
+        self.config.get().and_then(|c| c.large_allocation_threshold.get())
+    }
+
+    /// Give allocations of at least `threshold` bytes that don't fit in the
+    /// current chunk a chunk of their own, so that the current chunk's free
+    /// space keeps being used for smaller allocations.
+    ///
+    /// By default (with a threshold of `None`), an allocation that doesn't fit
+    /// in the current chunk makes a new, bigger chunk current, and whatever
+    /// space was left in the old chunk is never used. With a threshold set,
+    /// such a large allocation instead gets a chunk sized just for it, which
+    /// is linked in behind the current chunk, the same way as for
+    /// allocations with an alignment larger than a page. The current chunk
+    /// stays current, so subsequent small allocations carry on filling it.
+    ///
+    /// Allocations below the threshold, and allocations made when the arena
+    /// has no chunk yet, are not affected.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(1024);
+    /// bump.set_large_allocation_threshold(Some(512));
+    ///
+    /// bump.alloc([0_u8; 800]);
+    /// let free = bump.chunk_capacity();
+    ///
+    /// // This doesn't fit, but the remaining space isn't wasted.
+    /// bump.alloc([0_u8; 4096]);
+    /// assert_eq!(bump.chunk_capacity(), free);
+    /// bump.alloc([0_u8; 100]);
+    /// assert_eq!(bump.chunk_capacity(), free - 100);
+    /// ```
+    pub fn set_large_allocation_threshold(&self, threshold: Option<usize>) {
+        self.config().large_allocation_threshold.set(threshold);
+    }
+
+    /// Set a function to call, instead of panicking, when an infallible
+    /// allocation in this arena fails.
+    ///
+    /// This covers the methods that panic on allocation failure, like
+    /// [`alloc`](Bump::alloc), [`alloc_layout`](Bump::alloc_layout) and the
+    /// `alloc_slice_*` family, as well as growing the collections in
+    /// `bumpalo::collections` and creating a `bumpalo::boxed::Box`. The
+    /// fallible `try_*` methods are unaffected and still return an `Err`.
+    /// Pass `None` to go back to the default, which is to panic.
+    ///
+    /// The handler must diverge, as its `-> !` return type enforces: for
+    /// example, it can abort the process, or panic with its own payload that
+    /// an embedder (such as a fuzzing harness) catches and recognizes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    ///
+    /// struct OutOfArena;
+    ///
+    /// fn on_oom() -> ! {
+    ///     panic::panic_any(OutOfArena)
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(0));
+    /// bump.set_oom_handler(Some(on_oom));
+    ///
+    /// let err = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     bump.alloc(42);
+    /// }))
+    /// .unwrap_err();
+    /// assert!(err.is::<OutOfArena>());
+    /// ```
+    pub fn set_oom_handler(&self, handler: Option<fn() -> !>) {
+        self.config().oom_handler.set(handler);
+    }
+
+    /// Check that no allocation in this arena has been overrun.
+    ///
+    /// This does nothing unless the arena was created with
+    /// [`Bump::with_canaries`]. It is done automatically on
+    /// [`reset`][Bump::reset] and when the arena is dropped.
+    ///
+    /// Requires the `canaries` feature.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the canary bytes after any allocation have been overwritten.
+    /// The panic message includes the index of the overrun allocation,
+    /// counting from zero in allocation order since the arena was created or
+    /// last reset.
+    ///
+    /// ## Example
+    ///
+    /// ```should_panic
+    /// let bump = bumpalo::Bump::with_canaries();
+    /// let buf = bump.alloc_slice_fill_copy(4, 0_u8);
+    /// unsafe {
+    ///     // Write one byte past the end of `buf`.
+    ///     buf.as_mut_ptr().add(4).write(1);
+    /// }
+    /// bump.check_canaries(); // panics: allocation 0 was overrun
+    /// ```
+    #[cfg(feature = "canaries")]
+    pub fn check_canaries(&self) {
+        let mut record = match self.canaries.get() {
+            Some(state) => state.last,
+            None => return,
+        };
+        while let Some(r) = record {
+            unsafe {
+                let r = r.as_ref();
+                if r.canary.as_ptr().cast::<[u8; CANARY.len()]>().read() != CANARY {
+                    panic!("bumpalo: allocation {} was overrun", r.index);
+                }
+                record = r.prev;
+            }
+        }
+    }
+
+    /// Allocate `layout` followed by canary bytes, and record where the
+    /// canary is so that it can be checked later.
+    #[cfg(feature = "canaries")]
+    #[inline(never)]
+    fn alloc_layout_with_canary(
+        &self,
+        layout: Layout,
+        state: CanaryState,
+    ) -> Result<NonNull<u8>, AllocErr> {
+        let padded = layout
+            .size()
+            .checked_add(CANARY.len())
+            .and_then(|size| Layout::from_size_align(size, layout.align()).ok())
+            .ok_or(AllocErr)?;
+        let raw = |layout| {
+            self.try_alloc_layout_fast(layout)
+                .or_else(|| self.alloc_layout_slow(layout))
+                .ok_or(AllocErr)
+        };
+        let ptr = raw(padded)?;
+        // The record is allocated after the canary, so the guarded allocation
+        // is never the last one and can't be grown in place over its canary.
+        let record = raw(Layout::new::<CanaryRecord>())?.cast::<CanaryRecord>();
+        unsafe {
+            let canary = NonNull::new_unchecked(ptr.as_ptr().add(layout.size()));
+            canary.as_ptr().cast::<[u8; CANARY.len()]>().write(CANARY);
+            record.as_ptr().write(CanaryRecord {
+                canary,
+                index: state.count,
+                prev: state.last,
+            });
+        }
+        self.canaries.set(Some(CanaryState {
+            last: Some(record),
+            count: state.count + 1,
+        }));
+        Ok(ptr)
+    }
+
+    /// Handle failure of an infallible allocation, using the handler set by
+    /// `set_oom_handler` if there is one.
+    #[inline(never)]
+    #[cold]
+    pub(crate) fn oom(&self) -> ! {
+        match self.config.get().and_then(|c| c.oom_handler.get()) {
+            Some(handler) => handler(),
+            None => oom(),
+        }
+    }
+
+    /// Like `oom`, but for allocation failures that the collections report
+    /// with the failed layout.
+    #[cfg(feature = "collections")]
+    #[inline(never)]
+    #[cold]
+    pub(crate) fn handle_alloc_error(&self, layout: Layout) -> ! {
+        match self.config.get().and_then(|c| c.oom_handler.get()) {
+            Some(handler) => handler(),
+            None => alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// How much headroom an arena has before it hits its allocation
+    /// limit.
+    fn allocation_limit_remaining(&self) -> Option<usize> {
         self.allocation_limit.get().and_then(|allocation_limit| {
             let allocated_bytes = self.allocated_bytes();
             if allocated_bytes > allocation_limit {
@@ -855,7 +1879,8 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                 (new_size_without_footer + OVERHEAD).next_power_of_two() - OVERHEAD;
         } else {
             new_size_without_footer =
-                round_up_to(new_size_without_footer + OVERHEAD, TYPICAL_PAGE_SIZE)? - OVERHEAD;
+                round_up_to(new_size_without_footer.checked_add(OVERHEAD)?, TYPICAL_PAGE_SIZE)?
+                    - OVERHEAD;
         }
 
         debug_assert_eq!(align % CHUNK_ALIGN, 0);
@@ -880,6 +1905,7 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         new_chunk_memory_details: NewChunkMemoryDetails,
         requested_layout: Layout,
         prev: NonNull<ChunkFooter>,
+        chunk_source: Option<&dyn ChunkSource>,
     ) -> Option<NonNull<ChunkFooter>> {
         let NewChunkMemoryDetails {
             new_size_without_footer,
@@ -891,8 +1917,10 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
 
         debug_assert!(size >= requested_layout.size());
 
-        let data = alloc(layout);
-        let data = NonNull::new(data)?;
+        let data = match chunk_source {
+            Some(source) => source.alloc_chunk(layout)?,
+            None => NonNull::new(alloc(layout))?,
+        };
 
         // The `ChunkFooter` is at the end of the chunk.
         let footer_ptr = data.as_ptr().add(new_size_without_footer);
@@ -945,7 +1973,15 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// objects; see [the top-level documentation](struct.Bump.html) for details.
     ///
     /// If this arena has allocated multiple chunks to bump allocate into, then
-    /// the excess chunks are returned to the global allocator.
+    /// the excess chunks are returned to the global allocator (or to the
+    /// arena's [`ChunkSource`], if it has one).
+    ///
+    /// The chunk that is kept is the most recently allocated one, and its bump
+    /// pointer goes back to exactly where it started. So once the arena is
+    /// down to a single chunk, repeating the same sequence of allocations
+    /// after each reset (with the same sizes and alignments, and fitting in
+    /// that chunk) returns the same addresses every time. Patterns such as
+    /// double buffering through a reset arena can rely on this.
     ///
     /// ## Example
     ///
@@ -968,9 +2004,58 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     ///     bump.alloc(j);
     /// }
     ///```
+    ///
+    /// The same allocations land at the same addresses after each reset:
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(1024);
+    /// let first = bump.alloc_slice_fill_with(64, |i| i as u8).as_ptr();
+    /// bump.reset();
+    /// let second = bump.alloc_slice_fill_with(64, |i| i as u8).as_ptr();
+    /// assert_eq!(first, second);
+    /// ```
     pub fn reset(&mut self) {
         // Takes `&mut self` so `self` must be unique and there can't be any
         // borrows active that would get invalidated by resetting.
+        let this = &*self;
+        // Finish resetting even if a destructor panics, so that the arena is
+        // left empty rather than half reset.
+        let _finish = Finally(|| unsafe { this.finish_reset() });
+        unsafe {
+            this.run_drops_until(None);
+        }
+    }
+
+    /// Everything `reset` does after running the destructors.
+    ///
+    /// ## Safety
+    ///
+    /// Must only be called while resetting, with exclusive access to the arena
+    /// and after the destructors have run.
+    unsafe fn finish_reset(&self) {
+        #[cfg(feature = "canaries")]
+        if self.canaries.get().is_some() {
+            // Don't turn a panic that is already unwinding into an abort.
+            if !panicking() {
+                self.check_canaries();
+            }
+            self.canaries.set(Some(CanaryState {
+                last: None,
+                count: 0,
+            }));
+        }
+        self.last_tagged.set(None);
+        self.generation.set(fresh_generation());
+        #[cfg(feature = "std")]
+        if let Some(interner) = self.interner.borrow_mut().as_mut() {
+            interner.strings.clear();
+            interner.order.clear();
+        }
+        #[cfg(feature = "tracking")]
+        for bytes in &self.alignment_histogram {
+            bytes.set(0);
+        }
+
         unsafe {
             if self.current_chunk_footer.get().as_ref().is_empty() {
                 return;
@@ -980,7 +2065,7 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
 
             // Deallocate all chunks except the current one
             let prev_chunk = cur_chunk.as_ref().prev.replace(EMPTY_CHUNK.get());
-            dealloc_chunk_list(prev_chunk);
+            dealloc_chunk_list(prev_chunk, self.chunk_source());
 
             // Reset the bump finger to the end of the chunk.
             debug_assert!(
@@ -989,8 +2074,10 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
             );
             cur_chunk.as_ref().ptr.set(cur_chunk.cast());
 
-            // Reset the allocated size of the chunk.
-            cur_chunk.as_mut().allocated_bytes = cur_chunk.as_ref().layout.size();
+            // Reset the allocated size of the chunk. Like `new_chunk`, this
+            // counts the chunk's capacity, which excludes the footer.
+            cur_chunk.as_mut().allocated_bytes =
+                cur_chunk.as_ptr() as usize - cur_chunk.as_ref().data.as_ptr() as usize;
 
             debug_assert!(
                 self.current_chunk_footer
@@ -1008,11 +2095,250 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                 "Our chunk's bump finger should be reset to the start of its allocation"
             );
         }
+
+        self.first_chunk_footer.set(self.current_chunk_footer.get());
+    }
+
+    /// Reset this bump allocator, keeping only the first chunk it allocated.
+    ///
+    /// This is like [`reset`](Bump::reset), except that it keeps the arena's
+    /// first chunk (e.g. the one allocated by
+    /// [`with_capacity`](Bump::with_capacity)) instead of the current chunk.
+    /// All other chunks are deallocated, and allocation restarts from the
+    /// beginning of the first chunk.
+    ///
+    /// Since chunks grow as the arena does, the first chunk is usually the
+    /// smallest, while `reset` keeps the largest. So this retains less memory
+    /// across resets, at the cost of allocating new chunks again if the next
+    /// round of allocations outgrows the first chunk. It is most useful when
+    /// the first chunk was sized up front for the expected workload.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(1024);
+    /// let capacity = bump.chunk_capacity();
+    ///
+    /// for i in 0..10_000 {
+    ///     bump.alloc(i);
+    /// }
+    /// assert!(bump.chunk_capacities().count() > 1);
+    ///
+    /// bump.reset_keeping_first();
+    /// assert_eq!(bump.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+    /// assert_eq!(bump.chunk_capacity(), capacity);
+    /// ```
+    pub fn reset_keeping_first(&mut self) {
+        let this = &*self;
+        // The destructors must run before any chunk is freed, and the reset
+        // must finish even if one of them panics.
+        let _finish = Finally(|| unsafe {
+            let first = this.first_chunk_footer.get();
+            let current = this.current_chunk_footer.get();
+            if current != first {
+                // Unlink the chunks allocated after the first one, and free
+                // them.
+                let mut newer = current;
+                while newer.as_ref().prev.get() != first {
+                    newer = newer.as_ref().prev.get();
+                }
+                newer.as_ref().prev.set(EMPTY_CHUNK.get());
+                dealloc_chunk_list(current, this.chunk_source());
+
+                this.current_chunk_footer.set(first);
+            }
+
+            // Now the first chunk is the current one, and a regular reset
+            // frees anything behind it and rewinds its bump pointer.
+            this.finish_reset();
+        });
+        unsafe {
+            this.run_drops_until(None);
+        }
+    }
+
+    /// Reset this bump allocator, but don't keep a chunk bigger than
+    /// `max_bytes`.
+    ///
+    /// This is like [`reset`](Bump::reset), except that if the chunk that
+    /// `reset` would keep has a capacity of more than `max_bytes`, it is
+    /// deallocated and replaced with a chunk of `max_bytes` capacity (rounded
+    /// up the same way chunk sizes always are). With a `max_bytes` of zero no
+    /// chunk is kept at all.
+    ///
+    /// This caps how much memory an arena holds on to between resets, while
+    /// still letting it grow as needed when a workload occasionally spikes.
+    /// The replacement chunk is allocated eagerly; if that fails, the arena
+    /// is left without a chunk and allocates one on demand later, as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(1024);
+    ///
+    /// // A spike makes the arena grow a big chunk...
+    /// bump.alloc_slice_fill_copy(1 << 20, 0_u8);
+    /// bump.reset();
+    /// assert!(bump.total_capacity() >= 1 << 20);
+    ///
+    /// // ...which `reset_max_retained` doesn't keep.
+    /// bump.alloc_slice_fill_copy(1 << 20, 0_u8);
+    /// bump.reset_max_retained(4096);
+    /// assert!(bump.total_capacity() < 1 << 20);
+    /// assert!(bump.chunk_capacity() >= 4096);
+    /// ```
+    pub fn reset_max_retained(&mut self, max_bytes: usize) {
+        let this = &*self;
+        // The destructors must run before any chunk is freed, and the reset
+        // must finish even if one of them panics.
+        let _finish = Finally(|| unsafe {
+            this.finish_reset();
+            this.cap_retained_chunk(max_bytes);
+        });
+        unsafe {
+            this.run_drops_until(None);
+        }
+    }
+
+    /// Replace the arena's only chunk with one of `max_bytes` capacity if it
+    /// is any bigger than that.
+    ///
+    /// ## Safety
+    ///
+    /// Must only be called right after a reset, while the arena has at most
+    /// one chunk and nothing allocated in it.
+    unsafe fn cap_retained_chunk(&self, max_bytes: usize) {
+        let current = self.current_chunk_footer.get();
+        if current.as_ref().is_empty() {
+            return;
+        }
+
+        let replacement = if max_bytes == 0 {
+            None
+        } else {
+            let layout = match layout_from_size_align(max_bytes, MIN_ALIGN) {
+                Ok(layout) => layout,
+                Err(_) => return,
+            };
+            let details = match Self::new_chunk_memory_details(None, layout) {
+                Some(details) => details,
+                None => return,
+            };
+            if self.chunk_capacity() <= details.new_size_without_footer {
+                return;
+            }
+            Some((details, layout))
+        };
+
+        debug_assert!(current.as_ref().prev.get().as_ref().is_empty());
+        dealloc_chunk_list(current, self.chunk_source());
+        self.current_chunk_footer.set(EMPTY_CHUNK.get());
+        self.first_chunk_footer.set(EMPTY_CHUNK.get());
+
+        if let Some((details, layout)) = replacement {
+            if !Self::chunk_fits_under_limit(self.allocation_limit_remaining(), details) {
+                return;
+            }
+            if let Some(footer) =
+                Self::new_chunk(details, layout, EMPTY_CHUNK.get(), self.chunk_source())
+            {
+                self.current_chunk_footer.set(footer);
+                self.first_chunk_footer.set(footer);
+            }
+        }
+    }
+
+    /// Run `f` with a scope for allocating in this arena, and rewind the arena
+    /// to its current state once `f` returns.
+    ///
+    /// Everything allocated through the [`BumpScope`] handed to `f` is freed
+    /// when `f` returns (or unwinds): the bump pointer is moved back, and any
+    /// chunks allocated in the meantime are deallocated. The scope derefs to
+    /// the arena, so all of the usual allocation methods and collections can
+    /// be used with it, but the allocations borrow the scope and therefore
+    /// cannot escape `f`. `f`'s return value is passed through.
+    ///
+    /// Because this borrows the arena mutably, it can't be allocated from
+    /// directly while `f` runs. Scopes can be nested with
+    /// [`BumpScope::with_checkpoint`], and each one rewinds exactly the
+    /// allocations made within it, innermost first.
+    ///
+    /// Values allocated within the scope with plain [`alloc`](Bump::alloc)
+    /// are not dropped, as usual. Values allocated within the scope with
+    /// [`alloc_with_drop`](Bump::alloc_with_drop) are dropped, in reverse
+    /// order of allocation, when the scope ends.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(1024);
+    /// let capacity = bump.chunk_capacity();
+    ///
+    /// let sum = bump.with_checkpoint(|scope| {
+    ///     let xs = scope.alloc_slice_fill_with(100, |i| i as u64);
+    ///     xs.iter().sum::<u64>()
+    /// });
+    /// assert_eq!(sum, 4950);
+    ///
+    /// // The slice has been rewound.
+    /// assert_eq!(bump.chunk_capacity(), capacity);
+    /// ```
+    ///
+    /// Allocations can't outlive the scope:
+    ///
+    /// ```compile_fail
+    /// let mut bump = bumpalo::Bump::new();
+    /// let x = bump.with_checkpoint(|scope| scope.alloc(1));
+    /// ```
+    pub fn with_checkpoint<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut BumpScope<'_, MIN_ALIGN>) -> R,
+    {
+        // Safety: `self` is borrowed mutably, so nothing else allocates into
+        // it or holds on to its allocations until we return.
+        unsafe { self.run_checkpoint(f) }
+    }
+
+    /// Run `f` in a new scope, and rewind everything allocated since.
+    ///
+    /// ## Safety
+    ///
+    /// For as long as `f` runs, no allocations may be made in this arena
+    /// except through the scope passed to `f`, and no allocation made during
+    /// that time may be used once this returns.
+    unsafe fn run_checkpoint<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut BumpScope<'_, MIN_ALIGN>) -> R,
+    {
+        let footer = self.current_chunk_footer.get();
+        let _checkpoint = Checkpoint {
+            bump: self,
+            footer,
+            ptr: footer.as_ref().ptr.get(),
+            prev: footer.as_ref().prev.get(),
+            allocated_bytes: footer.as_ref().allocated_bytes,
+            last_tagged: self.last_tagged.get(),
+            last_drop: self.last_drop.get(),
+            first_chunk_footer: self.first_chunk_footer.get(),
+            #[cfg(feature = "canaries")]
+            canaries: self.canaries.get(),
+            #[cfg(feature = "std")]
+            interned: self.interner.borrow().as_ref().map_or(0, |i| i.order.len()),
+            #[cfg(feature = "tracking")]
+            alignment_histogram: core::array::from_fn(|i| self.alignment_histogram[i].get()),
+        };
+        f(&mut BumpScope {
+            bump: self,
+            lifetime: PhantomData,
+        })
     }
 
     /// Allocate an object in this `Bump` and return an exclusive reference to
     /// it.
     ///
+    /// Allocating a zero-sized type does not use any space in the arena. See
+    /// [`alloc_layout`](Bump::alloc_layout) for details.
+    ///
     /// ## Panics
     ///
     /// Panics if reserving space for `T` fails.
@@ -1024,6 +2350,23 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// let x = bump.alloc("hello");
     /// assert_eq!(*x, "hello");
     /// ```
+    ///
+    /// Closures can be allocated too, and the returned reference coerces to a
+    /// `dyn Fn`, `dyn FnMut` (or any other unsized trait object) reference
+    /// that lives as long as the arena borrow, without boxing:
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut total = 0;
+    /// let mut callbacks: Vec<&mut dyn FnMut(i32)> = Vec::new();
+    /// callbacks.push(bump.alloc(|x| total += x));
+    /// callbacks.push(bump.alloc(|x| println!("got {x}")));
+    /// for callback in &mut callbacks {
+    ///     callback(5);
+    /// }
+    /// drop(callbacks);
+    /// assert_eq!(total, 5);
+    /// ```
     #[inline(always)]
     pub fn alloc<T>(&self, val: T) -> &mut T {
         self.alloc_with(|| val)
@@ -1048,13 +2391,12 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         self.try_alloc_with(|| val)
     }
 
-    /// Pre-allocate space for an object in this `Bump`, initializes it using
-    /// the closure, then returns an exclusive reference to it.
+    /// Allocate an object in this `Bump` and return a shared reference to it.
     ///
-    /// See [The `_with` Method Suffix](#initializer-functions-the-_with-method-suffix) for a
-    /// discussion on the differences between the `_with` suffixed methods and
-    /// those methods without it, their performance characteristics, and when
-    /// you might or might not choose a `_with` suffixed method.
+    /// This is the same as [`alloc`](Bump::alloc), but is useful for values
+    /// that are immutable once created: the returned reference can be freely
+    /// copied and aliased for the arena's lifetime, and the type system rules
+    /// out accidental mutation.
     ///
     /// ## Panics
     ///
@@ -1064,23 +2406,166 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_with(|| "hello");
-    /// assert_eq!(*x, "hello");
+    /// let x: &i32 = bump.alloc_shared(42);
+    /// let y = x;
+    /// assert_eq!(*x + *y, 84);
     /// ```
     #[inline(always)]
-    pub fn alloc_with<F, T>(&self, f: F) -> &mut T
-    where
-        F: FnOnce() -> T,
-    {
-        #[inline(always)]
-        unsafe fn inner_writer<T, F>(ptr: *mut T, f: F)
-        where
-            F: FnOnce() -> T,
-        {
-            // This function is translated as:
-            // - allocate space for a T on the stack
-            // - call f() with the return value being put onto this stack space
-            // - memcpy from the stack to the heap
+    pub fn alloc_shared<T>(&self, val: T) -> &T {
+        self.alloc(val)
+    }
+
+    /// Allocate an object in this `Bump` and return a raw pointer to it.
+    ///
+    /// Unlike [`alloc`](Bump::alloc), the result does not borrow the arena, so
+    /// any number of pointers into the arena may coexist and point at each
+    /// other. This is the building block for intrusive and cyclic data
+    /// structures whose nodes link to one another through raw pointers.
+    ///
+    /// The pointer is valid for reads and writes of `T` until this `Bump` is
+    /// reset or dropped. It is the caller's responsibility not to use it after
+    /// that, and not to create a `&mut T` from it while any other reference to
+    /// the same value is alive.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::ptr::NonNull;
+    ///
+    /// struct Node {
+    ///     value: u32,
+    ///     next: Option<NonNull<Node>>,
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let first = bump.alloc_raw(Node { value: 1, next: None });
+    /// let second = bump.alloc_raw(Node { value: 2, next: Some(first) });
+    ///
+    /// // Close the cycle through the first node's pointer.
+    /// unsafe {
+    ///     (*first.as_ptr()).next = Some(second);
+    ///     let next = (*first.as_ptr()).next.unwrap();
+    ///     assert_eq!((*next.as_ptr()).value, 2);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn alloc_raw<T>(&self, val: T) -> NonNull<T> {
+        NonNull::from(self.alloc(val))
+    }
+
+    /// Same as `alloc_raw` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    #[inline(always)]
+    pub fn try_alloc_raw<T>(&self, val: T) -> Result<NonNull<T>, AllocErr> {
+        self.try_alloc(val).map(NonNull::from)
+    }
+
+    /// Returns this arena's current generation.
+    ///
+    /// The generation changes whenever allocations are freed in bulk: on
+    /// [`reset`](Bump::reset) and when a
+    /// [`with_checkpoint`](Bump::with_checkpoint) scope ends. Every arena
+    /// starts with a fresh generation, and generations are never reused by
+    /// any arena in the process, so two different arenas never share one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let before = bump.generation();
+    /// bump.reset();
+    /// assert_ne!(bump.generation(), before);
+    /// ```
+    #[cfg(target_has_atomic = "64")]
+    pub fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+
+    /// Allocate an object in this `Bump` and return a checked handle to it.
+    ///
+    /// Unlike the reference returned by [`alloc`](Bump::alloc), the handle
+    /// does not borrow the arena, so it can be kept across resets. Getting the
+    /// value back with [`AllocHandle::get`] checks that the arena is the one
+    /// the value was allocated in, and that it has not been reset since (see
+    /// [`generation`](Bump::generation)), returning `None` otherwise.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let handle = bump.alloc_checked(42);
+    /// assert_eq!(handle.get(&bump), Some(&42));
+    ///
+    /// bump.reset();
+    /// assert_eq!(handle.get(&bump), None);
+    /// ```
+    #[cfg(target_has_atomic = "64")]
+    #[inline(always)]
+    pub fn alloc_checked<T>(&self, val: T) -> AllocHandle<T> {
+        AllocHandle {
+            ptr: self.alloc_raw(val),
+            generation: self.generation.get(),
+        }
+    }
+
+    /// Same as `alloc_checked` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    #[cfg(target_has_atomic = "64")]
+    #[inline(always)]
+    pub fn try_alloc_checked<T>(&self, val: T) -> Result<AllocHandle<T>, AllocErr> {
+        Ok(AllocHandle {
+            ptr: self.try_alloc_raw(val)?,
+            generation: self.generation.get(),
+        })
+    }
+
+    /// Pre-allocate space for an object in this `Bump`, initializes it using
+    /// the closure, then returns an exclusive reference to it.
+    ///
+    /// See [The `_with` Method Suffix](#initializer-functions-the-_with-method-suffix) for a
+    /// discussion on the differences between the `_with` suffixed methods and
+    /// those methods without it, their performance characteristics, and when
+    /// you might or might not choose a `_with` suffixed method.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_with(|| "hello");
+    /// assert_eq!(*x, "hello");
+    /// ```
+    #[inline(always)]
+    pub fn alloc_with<F, T>(&self, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        #[inline(always)]
+        unsafe fn inner_writer<T, F>(ptr: *mut T, f: F)
+        where
+            F: FnOnce() -> T,
+        {
+            // This function is translated as:
+            // - allocate space for a T on the stack
+            // - call f() with the return value being put onto this stack space
+            // - memcpy from the stack to the heap
             //
             // Ideally we want LLVM to always realize that doing a stack
             // allocation is unnecessary and optimize the code so it writes
@@ -1103,6 +2588,8 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// Tries to pre-allocate space for an object in this `Bump`, initializes
     /// it using the closure, then returns an exclusive reference to it.
     ///
+    /// Iff the allocation fails, the closure is not run.
+    ///
     /// See [The `_with` Method Suffix](#initializer-functions-the-_with-method-suffix) for a
     /// discussion on the differences between the `_with` suffixed methods and
     /// those methods without it, their performance characteristics, and when
@@ -1368,214 +2855,1092 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         }
     }
 
-    /// `Copy` a slice into this `Bump` and return an exclusive reference to
-    /// the copy.
+    /// Allocate an object in this `Bump` followed by at least `extra_bytes` of
+    /// uninitialized trailing space, and return exclusive references to both.
+    ///
+    /// The value sits at the start of the allocation and the trailing space
+    /// directly follows it. The trailing slice also includes any padding the
+    /// arena reserved beyond `size_of::<T>() + extra_bytes` (see
+    /// [`alloc_layout_excess`](Bump::alloc_layout_excess)), so its length is
+    /// the actual trailing capacity. This supports "header plus inline
+    /// buffer" structures without a separate `Vec`.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Panics if reserving space for the value and trailing bytes fails.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_copy(&[1, 2, 3]);
-    /// assert_eq!(x, &[1, 2, 3]);
+    /// let (len, buf) = bump.alloc_with_trailing_capacity(0_u32, 6);
+    /// assert!(buf.len() >= 6);
+    ///
+    /// for (slot, byte) in buf.iter_mut().zip(b"arena!") {
+    ///     slot.write(*byte);
+    ///     *len += 1;
+    /// }
+    /// assert_eq!(*len, 6);
     /// ```
-    #[inline(always)]
-    pub fn alloc_slice_copy<T>(&self, src: &[T]) -> &mut [T]
-    where
-        T: Copy,
-    {
-        let layout = Layout::for_value(src);
-        let dst = self.alloc_layout(layout).cast::<T>();
+    #[inline]
+    pub fn alloc_with_trailing_capacity<T>(
+        &self,
+        val: T,
+        extra_bytes: usize,
+    ) -> (&mut T, &mut [mem::MaybeUninit<u8>]) {
+        let size = mem::size_of::<T>()
+            .checked_add(extra_bytes)
+            .unwrap_or_else(|| self.oom());
+        let layout =
+            Layout::from_size_align(size, mem::align_of::<T>()).unwrap_or_else(|_| self.oom());
+        let (p, excess) = self.alloc_layout_excess(layout);
 
         unsafe {
-            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
-            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+            let value = p.cast::<T>().as_ptr();
+            ptr::write(value, val);
+            let trailing = slice::from_raw_parts_mut(
+                p.as_ptr().add(mem::size_of::<T>()) as *mut mem::MaybeUninit<u8>,
+                extra_bytes + excess,
+            );
+            (&mut *value, trailing)
         }
     }
 
-    /// Like `alloc_slice_copy`, but does not panic in case of allocation failure.
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// let bump = bumpalo::Bump::new();
-    /// let x = bump.try_alloc_slice_copy(&[1, 2, 3]);
-    /// assert_eq!(x, Ok(&mut[1, 2, 3] as &mut [_]));
+    /// Allocate a value that needs to know its own address while it is being
+    /// constructed, and return an exclusive reference to it.
     ///
-    ///
-    /// let bump = bumpalo::Bump::new();
-    /// bump.set_allocation_limit(Some(4));
-    /// let x = bump.try_alloc_slice_copy(&[1, 2, 3, 4, 5, 6]);
-    /// assert_eq!(x, Err(bumpalo::AllocErr)); // too big
-    /// ```
-    #[inline(always)]
-    pub fn try_alloc_slice_copy<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
-    where
-        T: Copy,
-    {
-        let layout = Layout::for_value(src);
-        let dst = self.try_alloc_layout(layout)?.cast::<T>();
-        let result = unsafe {
-            core::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
-            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
-        };
-        Ok(result)
-    }
-
-    /// `Clone` a slice into this `Bump` and return an exclusive reference to
-    /// the clone. Prefer [`alloc_slice_copy`](#method.alloc_slice_copy) if `T` is `Copy`.
+    /// Space for a `T` is reserved first, and then `f` is called with a
+    /// [`CyclicSlot`] for that space. The slot only hands out a raw pointer
+    /// to the not-yet-initialized value, so `f` can record the value's final
+    /// address (e.g. for intrusive data structures) but cannot read from it.
+    /// The value returned by `f` is then written into the reserved space.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Panics if reserving space for `T` fails.
     ///
     /// ## Example
     ///
     /// ```
-    /// #[derive(Clone, Debug, Eq, PartialEq)]
-    /// struct Sheep {
-    ///     name: String,
+    /// struct Node {
+    ///     this: *const Node,
+    ///     value: u32,
     /// }
     ///
-    /// let originals = [
-    ///     Sheep { name: "Alice".into() },
-    ///     Sheep { name: "Bob".into() },
-    ///     Sheep { name: "Cathy".into() },
-    /// ];
-    ///
     /// let bump = bumpalo::Bump::new();
-    /// let clones = bump.alloc_slice_clone(&originals);
-    /// assert_eq!(originals, clones);
+    /// let node = bump.alloc_cyclic(|slot| Node {
+    ///     this: slot.as_ptr(),
+    ///     value: 42,
+    /// });
+    /// assert_eq!(node.this, node as *const Node);
+    /// assert_eq!(unsafe { (*node.this).value }, 42);
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_clone<T>(&self, src: &[T]) -> &mut [T]
+    pub fn alloc_cyclic<'a, T, F>(&'a self, f: F) -> &'a mut T
     where
-        T: Clone,
+        F: FnOnce(CyclicSlot<'a, T>) -> T,
     {
-        let layout = Layout::for_value(src);
-        let dst = self.alloc_layout(layout).cast::<T>();
+        let p = self.alloc_layout(Layout::new::<T>()).cast::<T>();
+        let slot = CyclicSlot {
+            ptr: p,
+            bump: PhantomData,
+        };
 
         unsafe {
-            for (i, val) in src.iter().cloned().enumerate() {
-                ptr::write(dst.as_ptr().add(i), val);
-            }
-
-            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+            ptr::write(p.as_ptr(), f(slot));
+            &mut *p.as_ptr()
         }
     }
 
-    /// Like `alloc_slice_clone` but does not panic on failure.
+    /// Like `alloc_cyclic` but does not panic on failure.
+    ///
+    /// If reserving space for `T` fails, `f` is not called.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
     #[inline(always)]
-    pub fn try_alloc_slice_clone<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
+    pub fn try_alloc_cyclic<'a, T, F>(&'a self, f: F) -> Result<&'a mut T, AllocErr>
     where
-        T: Clone,
+        F: FnOnce(CyclicSlot<'a, T>) -> T,
     {
-        let layout = Layout::for_value(src);
-        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+        let p = self.try_alloc_layout(Layout::new::<T>())?.cast::<T>();
+        let slot = CyclicSlot {
+            ptr: p,
+            bump: PhantomData,
+        };
 
         unsafe {
-            for (i, val) in src.iter().cloned().enumerate() {
-                ptr::write(dst.as_ptr().add(i), val);
-            }
-
-            Ok(slice::from_raw_parts_mut(dst.as_ptr(), src.len()))
+            ptr::write(p.as_ptr(), f(slot));
+            Ok(&mut *p.as_ptr())
         }
     }
 
-    /// `Copy` a string slice into this `Bump` and return an exclusive reference to it.
+    /// Allocate an object in this `Bump` under the given `tag`, and return a
+    /// shared reference to it.
+    ///
+    /// Tagged allocations can later be visited with
+    /// [`iter_tagged`](Bump::iter_tagged), which lets a single arena hold a
+    /// mix of types and still walk just the values of one kind.
+    ///
+    /// Each tagged allocation is prefixed with a small header holding the
+    /// tag, the value's type, and a link to the previous tagged allocation.
+    /// On 64-bit targets this costs 32 bytes per allocation, plus any padding
+    /// needed to align `T` after it.
+    ///
+    /// `T` must be `Send`, since the arena can be sent to another thread and
+    /// the value reached there through `iter_tagged`.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the string fails.
+    /// Panics if reserving space for the header and `T` fails.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let hello = bump.alloc_str("hello world");
-    /// assert_eq!("hello world", hello);
-    /// ```
-    #[inline(always)]
-    pub fn alloc_str(&self, src: &str) -> &mut str {
-        let buffer = self.alloc_slice_copy(src.as_bytes());
-        unsafe {
-            // This is OK, because it already came in as str, so it is guaranteed to be utf8
-            str::from_utf8_unchecked_mut(buffer)
-        }
-    }
-
-    /// Same as `alloc_str` but does not panic on failure.
-    ///
-    /// ## Example
+    /// bump.alloc_tagged(1, "hello");
+    /// bump.alloc_tagged(2, "world");
+    /// bump.alloc_tagged(1, "goodbye");
     ///
+    /// let ones: Vec<_> = bump.iter_tagged::<&str>(1).collect();
+    /// assert_eq!(ones, [&"goodbye", &"hello"]);
     /// ```
-    /// let bump = bumpalo::Bump::new();
-    /// let hello = bump.try_alloc_str("hello world").unwrap();
-    /// assert_eq!("hello world", hello);
     ///
+    /// Values that can't be sent to another thread can't be tagged:
     ///
+    /// ```compile_fail
     /// let bump = bumpalo::Bump::new();
-    /// bump.set_allocation_limit(Some(5));
-    /// let hello = bump.try_alloc_str("hello world");
-    /// assert_eq!(Err(bumpalo::AllocErr), hello);
+    /// bump.alloc_tagged(1, std::rc::Rc::new(1));
     /// ```
-    #[inline(always)]
-    pub fn try_alloc_str(&self, src: &str) -> Result<&mut str, AllocErr> {
-        let buffer = self.try_alloc_slice_copy(src.as_bytes())?;
-        unsafe {
-            // This is OK, because it already came in as str, so it is guaranteed to be utf8
-            Ok(str::from_utf8_unchecked_mut(buffer))
-        }
+    #[inline]
+    pub fn alloc_tagged<T: Send + 'static>(&self, tag: u32, val: T) -> &T {
+        let tagged = NonNull::from(self.alloc(Tagged {
+            header: TagHeader {
+                tag,
+                type_id: TypeId::of::<T>(),
+                prev: self.last_tagged.get(),
+            },
+            value: val,
+        }));
+        // Link the whole allocation, not just its header, so that the list can
+        // be used to get back to the value.
+        self.last_tagged.set(Some(tagged.cast()));
+        unsafe { &(*tagged.as_ptr()).value }
     }
 
-    /// Allocates a new slice of size `len` into this `Bump` and returns an
-    /// exclusive reference to the copy.
-    ///
-    /// The elements of the slice are initialized using the supplied closure.
-    /// The closure argument is the position in the slice.
-    ///
-    /// ## Panics
+    /// Iterate over the values of type `T` that were allocated with
+    /// [`alloc_tagged`](Bump::alloc_tagged) under the given `tag`, most
+    /// recently allocated first.
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Tagged values of other types are skipped, even if their tag matches.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_fill_with(5, |i| 5 * (i + 1));
-    /// assert_eq!(x, &[5, 10, 15, 20, 25]);
+    /// bump.alloc_tagged(7, 1_u32);
+    /// bump.alloc_tagged(7, 'x');
+    /// bump.alloc_tagged(7, 2_u32);
+    ///
+    /// let sum: u32 = bump.iter_tagged::<u32>(7).sum();
+    /// assert_eq!(sum, 3);
     /// ```
-    #[inline(always)]
-    pub fn alloc_slice_fill_with<T, F>(&self, len: usize, mut f: F) -> &mut [T]
-    where
-        F: FnMut(usize) -> T,
-    {
-        let layout = Layout::array::<T>(len).unwrap_or_else(|_| oom());
-        let dst = self.alloc_layout(layout).cast::<T>();
-
-        unsafe {
-            for i in 0..len {
-                ptr::write(dst.as_ptr().add(i), f(i));
-            }
-
-            let result = slice::from_raw_parts_mut(dst.as_ptr(), len);
-            debug_assert_eq!(Layout::for_value(result), layout);
-            result
+    pub fn iter_tagged<T: 'static>(&self, tag: u32) -> TaggedIter<'_, T> {
+        TaggedIter {
+            next: self.last_tagged.get(),
+            tag,
+            value: PhantomData,
         }
     }
 
-    /// Allocates a new slice of size `len` into this `Bump` and returns an
-    /// exclusive reference to the copy, failing if the closure return an Err.
+    /// Allocate an object in this `Bump` whose destructor is run when the
+    /// arena frees it, and return an exclusive reference to it.
     ///
-    /// The elements of the slice are initialized using the supplied closure.
-    /// The closure argument is the position in the slice.
+    /// Unlike values allocated with [`alloc`](Bump::alloc), the value is
+    /// dropped on [`reset`](Bump::reset), when the arena is dropped, and at the
+    /// end of a [`with_checkpoint`](Bump::with_checkpoint) scope it was
+    /// allocated in. Destructors run in reverse allocation order, before the
+    /// value's memory is reclaimed.
+    ///
+    /// If `T` needs dropping, the value is prefixed with a small header that
+    /// links it into a list of values to drop. On 64-bit targets this costs 16
+    /// bytes per allocation, plus any padding needed to align `T` after it.
+    ///
+    /// `T` must be `Send` and `'static` since it is dropped by whoever owns the
+    /// arena at that time, which may be another thread, long after any borrows
+    /// that `T` could hold have ended.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Panics if reserving space for `T` (and its header) fails.
+    ///
+    /// If a destructor panics while the arena is freeing values, the remaining
+    /// values are still dropped and the reset, drop or rewind of the arena
+    /// still completes before the panic propagates, like when dropping a
+    /// `Vec`. If a second destructor panics in the meantime, the process
+    /// aborts.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// struct CountDrops(Arc<AtomicUsize>);
+    ///
+    /// impl Drop for CountDrops {
+    ///     fn drop(&mut self) {
+    ///         self.0.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// let drops = Arc::new(AtomicUsize::new(0));
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.alloc_with_drop(CountDrops(drops.clone()));
+    /// bump.alloc(CountDrops(drops.clone()));
+    ///
+    /// bump.reset();
+    /// assert_eq!(drops.load(Ordering::SeqCst), 1);
+    /// ```
+    #[inline]
+    pub fn alloc_with_drop<T: Send + 'static>(&self, val: T) -> &mut T {
+        self.try_alloc_with_drop(val).unwrap_or_else(|_| self.oom())
+    }
+
+    /// Like [`alloc_with_drop`](Bump::alloc_with_drop), but does not panic in
+    /// case of allocation failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` (and its header) fails. The value is
+    /// dropped before the error is returned.
+    #[inline]
+    pub fn try_alloc_with_drop<T: Send + 'static>(&self, val: T) -> Result<&mut T, AllocErr> {
+        if !mem::needs_drop::<T>() {
+            return self.try_alloc(val);
+        }
+        let with_drop = NonNull::from(self.try_alloc(WithDrop {
+            header: DropHeader {
+                drop_value: drop_with_drop_value::<T>,
+                prev: self.last_drop.get(),
+            },
+            value: val,
+        })?);
+        // Link the whole allocation, not just its header, since the value is
+        // dropped through this pointer.
+        self.last_drop.set(Some(with_drop.cast()));
+        unsafe { Ok(&mut (*with_drop.as_ptr()).value) }
+    }
+
+    /// Drop the values allocated with `alloc_with_drop` since `stop` was the
+    /// most recent one, most recent first.
+    ///
+    /// If a destructor panics, the remaining values are still dropped before
+    /// the panic propagates, like the elements of a slice are. A second panic
+    /// aborts.
+    ///
+    /// ## Safety
+    ///
+    /// `stop` must be `None` or in the list of values to drop, and the values
+    /// being dropped must not be used afterwards.
+    unsafe fn run_drops_until(&self, stop: Option<NonNull<DropHeader>>) {
+        // Unlink the values first, so that none of them is dropped twice if a
+        // destructor panics.
+        let next = self.last_drop.replace(stop);
+        run_drops(next, stop);
+    }
+
+    /// `Copy` a slice into this `Bump` and return an exclusive reference to
+    /// the copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_copy(&[1, 2, 3]);
+    /// assert_eq!(x, &[1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_copy<T>(&self, src: &[T]) -> &mut [T]
+    where
+        T: Copy,
+    {
+        let layout = Layout::for_value(src);
+        let dst = self.alloc_layout(layout).cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        }
+    }
+
+    /// Like `alloc_slice_copy`, but does not panic in case of allocation failure.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_copy(&[1, 2, 3]);
+    /// assert_eq!(x, Ok(&mut[1, 2, 3] as &mut [_]));
+    ///
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(4));
+    /// let x = bump.try_alloc_slice_copy(&[1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(x, Err(bumpalo::AllocErr)); // too big
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_copy<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
+    where
+        T: Copy,
+    {
+        let layout = Layout::for_value(src);
+        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+        let result = unsafe {
+            core::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        };
+        Ok(result)
+    }
+
+    /// Like [`alloc_slice_copy`](Bump::alloc_slice_copy), but returns a shared
+    /// reference.
+    ///
+    /// The `&mut [T]` returned by `alloc_slice_copy` can always be turned into
+    /// a `&[T]`, and this method just does that for you. It is meant for
+    /// read-only data, like lookup tables that are built once and then handed
+    /// out to many readers, where the shared reference can be copied freely.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let table = bump.alloc_slice_copy_shared(&[1, 2, 3]);
+    /// let (a, b) = (table, table);
+    /// assert_eq!(a, b);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_copy_shared<T>(&self, src: &[T]) -> &[T]
+    where
+        T: Copy,
+    {
+        self.alloc_slice_copy(src)
+    }
+
+    /// Copy a slice of values that are not `Copy` into this `Bump`, bit for
+    /// bit, and return an exclusive reference to the copy.
+    ///
+    /// This is [`alloc_slice_copy`](Bump::alloc_slice_copy) for types that
+    /// could be `Copy` but aren't, for example plain-old-data types from FFI
+    /// bindings that only implement `Clone`, or types that don't implement
+    /// `Copy` to keep it out of their public API. The elements are copied
+    /// with a single `memcpy`, without calling `Clone::clone`.
+    ///
+    /// ## Safety
+    ///
+    /// Copying the bits of a `T` must produce a valid, independent `T`, just
+    /// like it does for `Copy` types, so that both the originals in `src` and
+    /// the copies can be used afterwards. In particular `T` must not:
+    ///
+    /// * have drop glue: the originals and the copies would both be dropped
+    ///   (if the copies are dropped at all), freeing the same resources twice;
+    /// * own anything uniquely, like a `Box`, a `String` or a `&mut`
+    ///   reference, since it would then be aliased;
+    /// * rely on `Clone::clone` to maintain an invariant, like a reference
+    ///   count or a pointer into the value itself.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// In debug builds, also panics if `T` has drop glue, since such types
+    /// are never safe to copy like this.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// // A plain-old-data type that is deliberately not `Copy`.
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+    /// // Safety: `Point` only contains `i32`s, so copying its bits is fine.
+    /// let copy = unsafe { bump.alloc_slice_copy_unchecked(&points) };
+    /// assert_eq!(copy, &points);
+    /// ```
+    #[inline(always)]
+    pub unsafe fn alloc_slice_copy_unchecked<T>(&self, src: &[T]) -> &mut [T] {
+        debug_assert!(
+            !mem::needs_drop::<T>(),
+            "alloc_slice_copy_unchecked called with a type that needs dropping"
+        );
+        let layout = Layout::for_value(src);
+        let dst = self.alloc_layout(layout).cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+
+        ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+        slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+    }
+
+    /// `Copy` a slice into this `Bump`, followed by `extra` uninitialized
+    /// slots, and return exclusive references to both parts.
+    ///
+    /// This suits layouts with a known prefix and a computed suffix, which can
+    /// then be built in a single allocation.
+    ///
+    /// The two returned slices are contiguous: the uninitialized suffix starts
+    /// right where the copied prefix ends. Note that each slice only gives
+    /// access to its own elements, so the prefix's pointer can't be used to
+    /// read the suffix, or the other way around.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails, or if `src.len() +
+    /// extra` overflows.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let (prefix, suffix) = bump.alloc_slice_copy_with_extra(&[1, 2], 3);
+    /// assert_eq!(prefix, &[1, 2]);
+    /// assert_eq!(prefix.as_ptr_range().end, suffix.as_ptr().cast());
+    ///
+    /// for (i, slot) in suffix.iter_mut().enumerate() {
+    ///     slot.write(10 * i);
+    /// }
+    /// let suffix = unsafe { &*(suffix as *const _ as *const [usize]) };
+    /// assert_eq!(suffix, &[0, 10, 20]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_copy_with_extra<T>(
+        &self,
+        src: &[T],
+        extra: usize,
+    ) -> (&mut [T], &mut [mem::MaybeUninit<T>])
+    where
+        T: Copy,
+    {
+        self.try_alloc_slice_copy_with_extra(src, extra)
+            .unwrap_or_else(|_| self.oom())
+    }
+
+    /// Like [`alloc_slice_copy_with_extra`](Bump::alloc_slice_copy_with_extra),
+    /// but does not panic in case of allocation failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails, or if `src.len() +
+    /// extra` overflows.
+    pub fn try_alloc_slice_copy_with_extra<T>(
+        &self,
+        src: &[T],
+        extra: usize,
+    ) -> Result<(&mut [T], &mut [mem::MaybeUninit<T>]), AllocErr>
+    where
+        T: Copy,
+    {
+        let len = src.len().checked_add(extra).ok_or(AllocErr)?;
+        let dst = self.try_reserve_slice_uninit::<T>(len)?;
+        debug_assert_nonoverlapping(src, NonNull::from(&mut *dst).cast::<T>());
+        let (prefix, suffix) = dst.split_at_mut(src.len());
+        let prefix = unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), prefix.as_mut_ptr().cast::<T>(), src.len());
+            &mut *(prefix as *mut [mem::MaybeUninit<T>] as *mut [T])
+        };
+        Ok((prefix, suffix))
+    }
+
+    /// `Copy` a slice into this `Bump` at an address aligned to at least
+    /// `align`, and return an exclusive reference to the copy.
+    ///
+    /// The copy is aligned to the larger of `align` and `T`'s own alignment.
+    /// This is for buffers whose alignment matters beyond what the element
+    /// type needs, such as SIMD or DMA buffers, or copies of page-aligned data
+    /// that should stay page-aligned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two, or if reserving space for the
+    /// slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_copy_aligned(&[1_u8, 2, 3], 64);
+    /// assert_eq!(x, &[1, 2, 3]);
+    /// assert_eq!(x.as_ptr() as usize % 64, 0);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_copy_aligned<T>(&self, src: &[T], align: usize) -> &mut [T]
+    where
+        T: Copy,
+    {
+        self.try_alloc_slice_copy_aligned(src, align)
+            .unwrap_or_else(|_| self.oom())
+    }
+
+    /// Like [`alloc_slice_copy_aligned`](Bump::alloc_slice_copy_aligned), but
+    /// does not panic in case of allocation failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn try_alloc_slice_copy_aligned<T>(
+        &self,
+        src: &[T],
+        align: usize,
+    ) -> Result<&mut [T], AllocErr>
+    where
+        T: Copy,
+    {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let layout = Layout::for_value(src)
+            .align_to(align)
+            .map_err(|_| AllocErr)?;
+        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            Ok(slice::from_raw_parts_mut(dst.as_ptr(), src.len()))
+        }
+    }
+
+    /// Copy raw bytes into this `Bump`, aligned for `T`, and return an
+    /// exclusive reference to them as a slice of `T`.
+    ///
+    /// This is useful for parsing binary formats, where a byte buffer holds
+    /// an array of fixed-size records. The bytes are copied, so `bytes` itself
+    /// need not be aligned.
+    ///
+    /// Returns `None` if `bytes.len()` is not a multiple of `size_of::<T>()`,
+    /// or if `T` is zero-sized.
+    ///
+    /// ## Safety
+    ///
+    /// Every `size_of::<T>()`-byte run of `bytes` must be a valid `T`. `Copy`
+    /// alone does not guarantee this: for example, only `0` and `1` are valid
+    /// `bool`s, and arbitrary bytes are never a valid reference. Types with
+    /// padding bytes are also a hazard, since padding is never initialized.
+    /// Plain integer, floating point, and arrays or `#[repr(C)]` structs of
+    /// those without padding are always fine.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let bytes: Vec<u8> = [1_u16, 2, 3].iter().flat_map(|n| n.to_ne_bytes()).collect();
+    ///
+    /// let words: &mut [u16] = unsafe { bump.alloc_slice_from_bytes(&bytes).unwrap() };
+    /// assert_eq!(words, [1, 2, 3]);
+    ///
+    /// // Six bytes can't hold a whole number of `u32`s.
+    /// assert!(unsafe { bump.alloc_slice_from_bytes::<u32>(&bytes) }.is_none());
+    /// ```
+    #[inline]
+    pub unsafe fn alloc_slice_from_bytes<T>(&self, bytes: &[u8]) -> Option<&mut [T]>
+    where
+        T: Copy,
+    {
+        let size = mem::size_of::<T>();
+        if size == 0 || bytes.len() % size != 0 {
+            return None;
+        }
+
+        let len = bytes.len() / size;
+        let layout = Layout::array::<T>(len).unwrap_or_else(|_| self.oom());
+        let dst = self.alloc_layout(layout).cast::<T>();
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dst.as_ptr().cast::<u8>(), bytes.len());
+        Some(slice::from_raw_parts_mut(dst.as_ptr(), len))
+    }
+
+    /// `Clone` a slice into this `Bump` and return an exclusive reference to
+    /// the clone. Prefer [`alloc_slice_copy`](#method.alloc_slice_copy) if `T` is `Copy`.
+    ///
+    /// `Clone::clone` is called once per element, in order. Stable Rust cannot
+    /// detect that `T` is `Copy` here, so this never switches to a `memcpy` on
+    /// its own, although trivial `clone` implementations usually optimize to
+    /// one anyway.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// #[derive(Clone, Debug, Eq, PartialEq)]
+    /// struct Sheep {
+    ///     name: String,
+    /// }
+    ///
+    /// let originals = [
+    ///     Sheep { name: "Alice".into() },
+    ///     Sheep { name: "Bob".into() },
+    ///     Sheep { name: "Cathy".into() },
+    /// ];
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let clones = bump.alloc_slice_clone(&originals);
+    /// assert_eq!(originals, clones);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_clone<T>(&self, src: &[T]) -> &mut [T]
+    where
+        T: Clone,
+    {
+        let layout = Layout::for_value(src);
+        let dst = self.alloc_layout(layout).cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+
+        unsafe {
+            for (i, val) in src.iter().cloned().enumerate() {
+                ptr::write(dst.as_ptr().add(i), val);
+            }
+
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        }
+    }
+
+    /// Like `alloc_slice_clone` but does not panic on failure.
+    #[inline(always)]
+    pub fn try_alloc_slice_clone<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
+    where
+        T: Clone,
+    {
+        let layout = Layout::for_value(src);
+        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+        debug_assert_nonoverlapping(src, dst);
+
+        unsafe {
+            for (i, val) in src.iter().cloned().enumerate() {
+                ptr::write(dst.as_ptr().add(i), val);
+            }
+
+            Ok(slice::from_raw_parts_mut(dst.as_ptr(), src.len()))
+        }
+    }
+
+    /// Like [`alloc_slice_clone`](Bump::alloc_slice_clone), but returns a
+    /// shared reference.
+    ///
+    /// See [`alloc_slice_copy_shared`](Bump::alloc_slice_copy_shared) for
+    /// why this is useful.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let names = bump.alloc_slice_clone_shared(&[String::from("a"), String::from("b")]);
+    /// let first = &names[0];
+    /// assert_eq!(first, "a");
+    /// assert_eq!(names, ["a", "b"]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_clone_shared<T>(&self, src: &[T]) -> &[T]
+    where
+        T: Clone,
+    {
+        self.alloc_slice_clone(src)
+    }
+
+    /// Place the elements of `src` into a new slice in this `Bump` and return
+    /// an exclusive reference to it.
+    ///
+    /// This is a single entry point for the common sources of slices: borrowed
+    /// slices and arrays are cloned element by element, as with
+    /// [`alloc_slice_clone`](Bump::alloc_slice_clone), while owned arrays and
+    /// `Vec`s have their elements moved into the arena. See
+    /// [`IntoArenaSlice`] for the full list. Cloning a slice of `Copy` values
+    /// compiles down to the same copy as
+    /// [`alloc_slice_copy`](Bump::alloc_slice_copy) does.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    ///
+    /// let numbers = [1, 2, 3];
+    /// assert_eq!(bump.alloc_slice(&numbers), &[1, 2, 3]);
+    ///
+    /// let names = vec![String::from("a"), String::from("b")];
+    /// assert_eq!(bump.alloc_slice(&names[..]), ["a", "b"]);
+    /// assert_eq!(bump.alloc_slice(names), ["a", "b"]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice<T, S>(&self, src: S) -> &mut [T]
+    where
+        S: IntoArenaSlice<T>,
+    {
+        self.try_alloc_slice(src).unwrap_or_else(|_| self.oom())
+    }
+
+    /// Same as `alloc_slice` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    #[inline(always)]
+    pub fn try_alloc_slice<T, S>(&self, src: S) -> Result<&mut [T], AllocErr>
+    where
+        S: IntoArenaSlice<T>,
+    {
+        src.try_alloc_in(self)
+    }
+
+    /// Copy an unsized value, such as a string slice or a slice of `Copy`
+    /// values, into this `Bump` and return an exclusive reference to the copy.
+    ///
+    /// This is a single entry point for the unsized types whose contents can
+    /// be copied byte for byte; see [`CopyUnsized`] for the full list. It does
+    /// the same as [`alloc_str`](Bump::alloc_str) or
+    /// [`alloc_slice_copy`](Bump::alloc_slice_copy), depending on the type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the copy fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    ///
+    /// let s: &mut str = bump.alloc_unsized("hello");
+    /// s.make_ascii_uppercase();
+    /// assert_eq!(s, "HELLO");
+    ///
+    /// let bytes: &mut [u8] = bump.alloc_unsized(&b"bytes"[..]);
+    /// bytes[0] = b'B';
+    /// assert_eq!(bytes, b"Bytes");
+    /// ```
+    #[inline(always)]
+    pub fn alloc_unsized<U>(&self, value: &U) -> &mut U
+    where
+        U: CopyUnsized + ?Sized,
+    {
+        self.try_alloc_unsized(value).unwrap_or_else(|_| self.oom())
+    }
+
+    /// Same as `alloc_unsized` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the copy fails.
+    #[inline(always)]
+    pub fn try_alloc_unsized<U>(&self, value: &U) -> Result<&mut U, AllocErr>
+    where
+        U: CopyUnsized + ?Sized,
+    {
+        value.try_copy_in(self)
+    }
+
+    /// `Copy` a string slice into this `Bump` and return an exclusive reference to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the string fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let hello = bump.alloc_str("hello world");
+    /// assert_eq!("hello world", hello);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_str(&self, src: &str) -> &mut str {
+        let buffer = self.alloc_slice_copy(src.as_bytes());
+        unsafe {
+            // This is OK, because it already came in as str, so it is guaranteed to be utf8
+            str::from_utf8_unchecked_mut(buffer)
+        }
+    }
+
+    /// Same as `alloc_str` but does not panic on failure.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let hello = bump.try_alloc_str("hello world").unwrap();
+    /// assert_eq!("hello world", hello);
+    ///
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(5));
+    /// let hello = bump.try_alloc_str("hello world");
+    /// assert_eq!(Err(bumpalo::AllocErr), hello);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_str(&self, src: &str) -> Result<&mut str, AllocErr> {
+        let buffer = self.try_alloc_slice_copy(src.as_bytes())?;
+        unsafe {
+            // This is OK, because it already came in as str, so it is guaranteed to be utf8
+            Ok(str::from_utf8_unchecked_mut(buffer))
+        }
+    }
+
+    /// Like [`alloc_str`](Bump::alloc_str), but returns a shared reference.
+    ///
+    /// See [`alloc_slice_copy_shared`](Bump::alloc_slice_copy_shared) for
+    /// why this is useful.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the string fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let greeting = bump.alloc_str_shared("hello");
+    /// let copy = greeting;
+    /// assert_eq!(greeting, copy);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_str_shared(&self, src: &str) -> &str {
+        self.alloc_str(src)
+    }
+
+    /// Copy a string slice into this `Bump`, unless an equal string was
+    /// interned before, and return a shared reference to the interned copy.
+    ///
+    /// In an arena created with [`Bump::with_interner`], every call with equal
+    /// strings returns the same reference, until the string is freed by a
+    /// [`reset`][Bump::reset] or the end of a
+    /// [`with_checkpoint`][Bump::with_checkpoint] scope. In any other arena,
+    /// this always allocates a new copy, like [`alloc_str`][Bump::alloc_str].
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the string fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_interner();
+    /// let idents: Vec<&str> = "a b a c b a"
+    ///     .split(' ')
+    ///     .map(|s| bump.alloc_str_interned(s))
+    ///     .collect();
+    /// assert!(std::ptr::eq(idents[0], idents[2]));
+    /// assert!(std::ptr::eq(idents[0], idents[5]));
+    /// assert!(!std::ptr::eq(idents[0], idents[1]));
+    /// assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), 3);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn alloc_str_interned(&self, src: &str) -> &str {
+        self.try_alloc_str_interned(src)
+            .unwrap_or_else(|_| self.oom())
+    }
+
+    /// Like [`alloc_str_interned`](Bump::alloc_str_interned), but does not
+    /// panic in case of allocation failure.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the string fails.
+    #[cfg(feature = "std")]
+    pub fn try_alloc_str_interned(&self, src: &str) -> Result<&str, AllocErr> {
+        let mut interner = self.interner.borrow_mut();
+        let interner = match interner.as_mut() {
+            Some(interner) => interner,
+            None => return self.try_alloc_str(src).map(|s| &*s),
+        };
+        if let Some(&interned) = interner.strings.get(src) {
+            return Ok(interned);
+        }
+        let copy: &str = self.try_alloc_str(src)?;
+        // Safety: the interner forgets this string before the arena frees it,
+        // in `reset` or when a checkpoint is rewound.
+        let copy: &'static str = unsafe { &*(copy as *const str) };
+        interner.strings.insert(copy);
+        interner.order.push(copy);
+        Ok(copy)
+    }
+
+    /// Copy the UTF-8 bytes of a string slice into this `Bump` and return an
+    /// exclusive reference to them as a byte slice.
+    ///
+    /// This is like [`alloc_str`](Bump::alloc_str) followed by `as_bytes`,
+    /// except that the bytes can be patched in place. The result is no longer
+    /// tied to being valid UTF-8.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the bytes fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let bytes = bump.alloc_str_bytes("hello world");
+    /// bytes[0] = b'j';
+    /// assert_eq!(bytes, b"jello world");
+    /// ```
+    #[inline(always)]
+    pub fn alloc_str_bytes(&self, src: &str) -> &mut [u8] {
+        self.alloc_slice_copy(src.as_bytes())
+    }
+
+    /// Same as `alloc_str_bytes` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the bytes fails.
+    #[inline(always)]
+    pub fn try_alloc_str_bytes(&self, src: &str) -> Result<&mut [u8], AllocErr> {
+        self.try_alloc_slice_copy(src.as_bytes())
+    }
+
+    /// Reserves space for a slice of `len` elements of type `T` in this `Bump`
+    /// and returns an exclusive reference to the uninitialized storage.
+    ///
+    /// The returned slice is aligned to both `T`'s alignment and the arena's
+    /// `MIN_ALIGN`. This is the primitive that the `alloc_slice_fill_*` methods
+    /// are built upon, and is useful for initializing a slice in ways they do
+    /// not support, for example by handing the storage to FFI.
+    ///
+    /// Initializing the elements is the caller's responsibility: only call
+    /// `MaybeUninit::assume_init` (or otherwise read an element) once that
+    /// element has been written. Since the storage is uninitialized, `T`'s
+    /// destructor is never run for any of the elements.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let uninit = bump.reserve_slice_uninit::<u32>(3);
+    /// for (i, slot) in uninit.iter_mut().enumerate() {
+    ///     slot.write(i as u32 * 10);
+    /// }
+    /// let x = unsafe { &*(uninit as *const [MaybeUninit<u32>] as *const [u32]) };
+    /// assert_eq!(x, &[0, 10, 20]);
+    /// ```
+    #[inline(always)]
+    pub fn reserve_slice_uninit<T>(&self, len: usize) -> &mut [mem::MaybeUninit<T>] {
+        let layout = Layout::array::<T>(len).unwrap_or_else(|_| self.oom());
+        let dst = self.alloc_layout(layout).cast::<mem::MaybeUninit<T>>();
+        unsafe { slice::from_raw_parts_mut(dst.as_ptr(), len) }
+    }
+
+    /// Like `reserve_slice_uninit` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(4));
+    /// assert!(bump.try_reserve_slice_uninit::<u64>(10).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn try_reserve_slice_uninit<T>(
+        &self,
+        len: usize,
+    ) -> Result<&mut [mem::MaybeUninit<T>], AllocErr> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocErr)?;
+        let dst = self.try_alloc_layout(layout)?.cast::<mem::MaybeUninit<T>>();
+        unsafe { Ok(slice::from_raw_parts_mut(dst.as_ptr(), len)) }
+    }
+
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to the copy.
+    ///
+    /// The elements of the slice are initialized using the supplied closure.
+    /// The closure argument is the position in the slice.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// If the closure panics, the elements initialized so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_fill_with(5, |i| 5 * (i + 1));
+    /// assert_eq!(x, &[5, 10, 15, 20, 25]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_with<T, F>(&self, len: usize, f: F) -> &mut [T]
+    where
+        F: FnMut(usize) -> T,
+    {
+        fill_uninit_with(self.reserve_slice_uninit::<T>(len), f)
+    }
+
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to the copy, failing if the closure return an Err.
+    ///
+    /// The elements of the slice are initialized using the supplied closure.
+    /// The closure argument is the position in the slice.
+    ///
+    /// ## Errors
+    ///
+    /// Iff the closure returns an error, that error is forwarded by value. The
+    /// elements initialized so far are dropped, and the space reserved for the
+    /// slice is given back to the arena, as long as it is still the most
+    /// recent allocation in the current chunk. That is the case unless the
+    /// closure itself allocated from this `Bump`, in which case the space is
+    /// only reclaimed by the next [`reset`](Bump::reset). If a new chunk had
+    /// to be allocated to fit the slice, that chunk is kept for later
+    /// allocations; otherwise [`allocated_bytes`](Bump::allocated_bytes) is
+    /// unchanged.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// If the closure panics, the elements initialized so far are dropped
+    /// before the panic propagates.
     ///
     /// ## Example
     ///
@@ -1598,20 +3963,39 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     where
         F: FnMut(usize) -> Result<T, E>,
     {
-        let layout = Layout::array::<T>(len).unwrap_or_else(|_| oom());
+        let layout = Layout::array::<T>(len).unwrap_or_else(|_| self.oom());
+        let footer = self.current_chunk_footer.get();
+        let old_ptr = unsafe { footer.as_ref().ptr.get() };
         let base_ptr = self.alloc_layout(layout);
         let dst = base_ptr.cast::<T>();
 
         unsafe {
+            let mut guard = DropInitialized {
+                start: dst.as_ptr(),
+                len: 0,
+            };
             for i in 0..len {
                 match f(i) {
-                    Ok(el) => ptr::write(dst.as_ptr().add(i), el),
+                    Ok(el) => {
+                        ptr::write(dst.as_ptr().add(i), el);
+                        guard.len += 1;
+                    }
                     Err(e) => {
-                        self.dealloc(base_ptr, layout);
+                        drop(guard);
+                        if self.current_chunk_footer.get() == footer
+                            && self.is_last_allocation(base_ptr)
+                        {
+                            // Also give back the padding that aligning the
+                            // slice may have taken.
+                            footer.as_ref().ptr.set(old_ptr);
+                        } else {
+                            self.dealloc(base_ptr, layout);
+                        }
                         return Err(e);
                     }
                 }
             }
+            mem::forget(guard);
 
             let result = slice::from_raw_parts_mut(dst.as_ptr(), len);
             debug_assert_eq!(Layout::for_value(result), layout);
@@ -1625,6 +4009,11 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// The elements of the slice are initialized using the supplied closure.
     /// The closure argument is the position in the slice.
     ///
+    /// ## Panics
+    ///
+    /// If the closure panics, the elements initialized so far are dropped
+    /// before the panic propagates.
+    ///
     /// ## Example
     ///
     /// ```
@@ -1639,25 +4028,114 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// assert_eq!(x, Err(bumpalo::AllocErr));
     /// ```
     #[inline(always)]
-    pub fn try_alloc_slice_fill_with<T, F>(
-        &self,
-        len: usize,
-        mut f: F,
-    ) -> Result<&mut [T], AllocErr>
+    pub fn try_alloc_slice_fill_with<T, F>(&self, len: usize, f: F) -> Result<&mut [T], AllocErr>
     where
         F: FnMut(usize) -> T,
     {
-        let layout = Layout::array::<T>(len).map_err(|_| AllocErr)?;
-        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+        Ok(fill_uninit_with(
+            self.try_reserve_slice_uninit::<T>(len)?,
+            f,
+        ))
+    }
+
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to it, threading an accumulator through the
+    /// initialization of each element.
+    ///
+    /// The closure is called once per element, in order, with a mutable
+    /// reference to the accumulator (starting at `init`) and the position in
+    /// the slice. This allows computing running sums, cumulative offsets and
+    /// the like directly into arena storage.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// If the closure panics, the elements initialized so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let sizes = [4, 8, 2, 6];
+    /// let offsets = bump.alloc_slice_fill_fold(sizes.len(), 0, |offset, i| {
+    ///     let start = *offset;
+    ///     *offset += sizes[i];
+    ///     start
+    /// });
+    /// assert_eq!(offsets, &[0, 4, 12, 14]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_fold<T, S, F>(&self, len: usize, init: S, mut f: F) -> &mut [T]
+    where
+        F: FnMut(&mut S, usize) -> T,
+    {
+        let mut state = init;
+        self.alloc_slice_fill_with(len, |i| f(&mut state, i))
+    }
+
+    /// Allocates a new slice of at most `max_len` elements into this `Bump`
+    /// and returns an exclusive reference to it.
+    ///
+    /// The elements of the slice are initialized using the supplied closure,
+    /// which is called with the position in the slice until it returns `None`
+    /// or `max_len` elements have been produced. The returned slice contains
+    /// only the produced elements.
+    ///
+    /// Space for `max_len` elements is reserved up front. If the slice is
+    /// still the most recent allocation in the arena once the closure is done,
+    /// the unused space is given back to the arena; otherwise it is simply
+    /// left unused until the arena is reset.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// If the closure panics, the elements initialized so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let input = [3, 1, 4, 1, 0, 5, 9];
+    /// let x = bump.alloc_slice_fill_while(input.len(), |i| {
+    ///     // Stop at the zero sentinel.
+    ///     Some(input[i]).filter(|&n| n != 0)
+    /// });
+    /// assert_eq!(x, &[3, 1, 4, 1]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_while<T, F>(&self, max_len: usize, mut f: F) -> &mut [T]
+    where
+        F: FnMut(usize) -> Option<T>,
+    {
+        let layout = Layout::array::<T>(max_len).unwrap_or_else(|_| self.oom());
+        let dst = self.alloc_layout(layout).cast::<T>();
 
         unsafe {
-            for i in 0..len {
-                ptr::write(dst.as_ptr().add(i), f(i));
+            let mut guard = DropInitialized {
+                start: dst.as_ptr(),
+                len: 0,
+            };
+            while guard.len < max_len {
+                match f(guard.len) {
+                    Some(val) => ptr::write(dst.as_ptr().add(guard.len), val),
+                    None => break,
+                }
+                guard.len += 1;
             }
+            let len = guard.len;
+            mem::forget(guard);
 
-            let result = slice::from_raw_parts_mut(dst.as_ptr(), len);
-            debug_assert_eq!(Layout::for_value(result), layout);
-            Ok(result)
+            // If nothing else was allocated in the meantime, give the unused
+            // tail back to the arena.
+            let dst = self
+                .shrink_last_allocation_in_place(dst.cast(), layout, len * mem::size_of::<T>())
+                .map_or(dst, |p| p.cast());
+
+            slice::from_raw_parts_mut(dst.as_ptr(), len)
         }
     }
 
@@ -1666,6 +4144,9 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     ///
     /// All elements of the slice are initialized to `value`.
     ///
+    /// The fill is a plain loop of copies, which the compiler can turn into
+    /// vectorized stores, or a `memset` for bytes.
+    ///
     /// ## Panics
     ///
     /// Panics if reserving space for the slice fails.
@@ -1683,6 +4164,20 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     }
 
     /// Same as `alloc_slice_fill_copy` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if `len * size_of::<T>()` overflows, or if reserving space for
+    /// the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_fill_copy(3, 7_u32).unwrap();
+    /// assert_eq!(x, &[7, 7, 7]);
+    /// assert!(bump.try_alloc_slice_fill_copy(usize::MAX, 0_u64).is_err());
+    /// ```
     #[inline(always)]
     pub fn try_alloc_slice_fill_copy<T: Copy>(
         &self,
@@ -1717,6 +4212,16 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     }
 
     /// Like `alloc_slice_fill_clone` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if `len * size_of::<T>()` overflows, or if reserving space for
+    /// the slice fails.
+    ///
+    /// ## Panics
+    ///
+    /// If `value.clone()` panics, the clones made so far are dropped before the
+    /// panic propagates.
     #[inline(always)]
     pub fn try_alloc_slice_fill_clone<T: Clone>(
         &self,
@@ -1760,6 +4265,14 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     ///
     /// The elements are initialized using the supplied iterator.
     ///
+    /// ## Errors
+    ///
+    /// Iff the iterator yields an error, that error is forwarded by value.
+    /// As with [`alloc_slice_try_fill_with`](Bump::alloc_slice_try_fill_with),
+    /// the elements collected so far are dropped and the slice's space is
+    /// given back, unless the iterator allocated from this `Bump` in the
+    /// meantime.
+    ///
     /// ## Panics
     ///
     /// Panics if reserving space for the slice fails, or if the supplied
@@ -1819,6 +4332,162 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         })
     }
 
+    /// Allocates a new slice in this `Bump` holding at most `n` items pulled
+    /// from `iter`, and returns an exclusive reference to it.
+    ///
+    /// The iterator is borrowed rather than consumed, so it is left positioned
+    /// after the last item taken, ready for the next call. This makes it easy
+    /// to tile a long (or unbounded) stream into arena slices of at most `n`
+    /// elements each. A slice shorter than `n` means that the iterator ran
+    /// out.
+    ///
+    /// Space for `n` elements is reserved up front, and any unused tail is
+    /// given back to the arena.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// If the iterator panics, the items taken from it so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut iter = 0..7;
+    ///
+    /// let mut batches = Vec::new();
+    /// loop {
+    ///     let batch = bump.alloc_slice_fill_iter_n(&mut iter, 3);
+    ///     let done = batch.len() < 3;
+    ///     batches.push(batch);
+    ///     if done {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(batches, [&[0, 1, 2][..], &[3, 4, 5], &[6]]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_iter_n<T, I>(&self, iter: &mut I, n: usize) -> &mut [T]
+    where
+        I: Iterator<Item = T>,
+    {
+        self.alloc_slice_fill_while(n, |_| iter.next())
+    }
+
+    /// Collects the items of an iterator of unknown length into a new slice
+    /// in this `Bump` and returns an exclusive reference to it.
+    ///
+    /// Unlike [`try_alloc_slice_fill_iter`](Bump::try_alloc_slice_fill_iter),
+    /// the iterator need not be an `ExactSizeIterator`. Space is reserved up
+    /// front for the iterator's `size_hint` upper bound if that fits in the
+    /// current chunk, or else for its lower bound. When the reservation runs
+    /// out, it is at least doubled, in place if the slice is still the most
+    /// recent allocation and there is room, or else by moving to a fresh,
+    /// larger reservation. Once the iterator is exhausted, any excess capacity
+    /// is given back to the arena if the slice is still the most recent
+    /// allocation.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving or growing space for the slice fails. The elements
+    /// collected so far are dropped before the error is returned.
+    ///
+    /// ## Panics
+    ///
+    /// If the iterator panics, the elements collected so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let evens = bump.try_collect_slice((0..10).filter(|n| n % 2 == 0));
+    /// assert_eq!(evens, Ok(&mut [0, 2, 4, 6, 8][..]));
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(64));
+    /// assert!(bump.try_collect_slice((0..1000_u64).filter(|n| n % 2 == 0)).is_err());
+    /// ```
+    pub fn try_collect_slice<T, I>(&self, iter: I) -> Result<&mut [T], AllocErr>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Owns the partially collected slice until it is complete, dropping its
+        // elements and giving back its space on error or panic.
+        struct Partial<'a, T, const MIN_ALIGN: usize> {
+            bump: &'a Bump<MIN_ALIGN>,
+            ptr: NonNull<T>,
+            len: usize,
+            cap: usize,
+        }
+
+        impl<T, const MIN_ALIGN: usize> Drop for Partial<'_, T, MIN_ALIGN> {
+            fn drop(&mut self) {
+                unsafe {
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
+                    let layout = Layout::array::<T>(self.cap).unwrap();
+                    self.bump.dealloc(self.ptr.cast(), layout);
+                }
+            }
+        }
+
+        let mut iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        // Hints are often loose (`filter`, for example, always reports a lower
+        // bound of zero). When the upper bound fits in the current chunk,
+        // reserve all of it: the unused tail is given back at the end, so this
+        // costs nothing and saves growing the slice over and over.
+        let cap = match upper {
+            Some(upper)
+                if upper > lower
+                    && upper
+                        .checked_mul(mem::size_of::<T>())
+                        .and_then(|size| size.checked_add(mem::align_of::<T>()))
+                        .is_some_and(|size| size <= self.chunk_capacity()) =>
+            {
+                upper
+            }
+            _ => lower,
+        };
+        let mut partial = Partial {
+            bump: self,
+            ptr: NonNull::from(self.try_reserve_slice_uninit::<T>(cap)?).cast::<T>(),
+            len: 0,
+            cap,
+        };
+
+        while let Some(item) = iter.next() {
+            if partial.len == partial.cap {
+                // At least double, like `Vec`, and skip the tiny capacities
+                // that would otherwise be grown through one by one.
+                let additional = iter.size_hint().0.saturating_add(1);
+                let new_cap = partial
+                    .cap
+                    .checked_add(additional.max(partial.cap).max(MIN_COLLECT_CAPACITY))
+                    .ok_or(AllocErr)?;
+                let old_layout = Layout::array::<T>(partial.cap).unwrap();
+                let new_layout = Layout::array::<T>(new_cap).map_err(|_| AllocErr)?;
+                partial.ptr =
+                    unsafe { self.grow(partial.ptr.cast(), old_layout, new_layout)? }.cast();
+                partial.cap = new_cap;
+            }
+            unsafe { ptr::write(partial.ptr.as_ptr().add(partial.len), item) };
+            partial.len += 1;
+        }
+
+        let partial = mem::ManuallyDrop::new(partial);
+        unsafe {
+            let old_layout = Layout::array::<T>(partial.cap).unwrap();
+            let new_size = partial.len * mem::size_of::<T>();
+            let ptr = self
+                .shrink_last_allocation_in_place(partial.ptr.cast(), old_layout, new_size)
+                .map_or(partial.ptr, |p| p.cast());
+            Ok(slice::from_raw_parts_mut(ptr.as_ptr(), partial.len))
+        }
+    }
+
     /// Allocates a new slice of size `len` slice into this `Bump` and return an
     /// exclusive reference to the copy.
     ///
@@ -1843,6 +4512,27 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     }
 
     /// Like `alloc_slice_fill_default` but does not panic on failure.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Panics
+    ///
+    /// If `T::default()` panics, the elements initialized so far are dropped
+    /// before the panic propagates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_fill_default::<u32>(3);
+    /// assert_eq!(x, Ok(&mut [0, 0, 0][..]));
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(0));
+    /// assert!(bump.try_alloc_slice_fill_default::<u64>(1000).is_err());
+    /// ```
     #[inline(always)]
     pub fn try_alloc_slice_fill_default<T: Default>(
         &self,
@@ -1851,18 +4541,82 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         self.try_alloc_slice_fill_with(len, |_| T::default())
     }
 
+    /// Allocates a new slice containing `value` repeated `times` times into
+    /// this `Bump` and returns an exclusive reference to it.
+    ///
+    /// The resulting slice has `value.len() * times` elements. If `value` is
+    /// empty or `times` is zero, an empty slice is returned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the total length overflows or if reserving space for the
+    /// slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_repeat(&[1, 2], 3);
+    /// assert_eq!(x, &[1, 2, 1, 2, 1, 2]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_repeat<T: Copy>(&self, value: &[T], times: usize) -> &mut [T] {
+        let len = value.len().checked_mul(times).unwrap_or_else(|| self.oom());
+        let layout = Layout::array::<T>(len).unwrap_or_else(|_| self.oom());
+        let dst = self.alloc_layout(layout).cast::<T>();
+
+        unsafe {
+            write_repeated(dst.as_ptr(), value, len);
+            slice::from_raw_parts_mut(dst.as_ptr(), len)
+        }
+    }
+
+    /// Like `alloc_slice_repeat` but does not panic on failure.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_repeat(&[1, 2], 3);
+    /// assert_eq!(x, Ok(&mut [1, 2, 1, 2, 1, 2] as &mut [_]));
+    ///
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_repeat(&[1, 2], usize::MAX);
+    /// assert_eq!(x, Err(bumpalo::AllocErr)); // length overflows
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_repeat<T: Copy>(
+        &self,
+        value: &[T],
+        times: usize,
+    ) -> Result<&mut [T], AllocErr> {
+        let len = value.len().checked_mul(times).ok_or(AllocErr)?;
+        let layout = Layout::array::<T>(len).map_err(|_| AllocErr)?;
+        let dst = self.try_alloc_layout(layout)?.cast::<T>();
+
+        unsafe {
+            write_repeated(dst.as_ptr(), value, len);
+            Ok(slice::from_raw_parts_mut(dst.as_ptr(), len))
+        }
+    }
+
     /// Allocate space for an object with the given `Layout`.
     ///
     /// The returned pointer points at uninitialized memory, and should be
     /// initialized with
     /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
     ///
+    /// Zero-sized layouts never touch the arena: they do not move the bump
+    /// pointer or allocate a new chunk, and get a dangling (but non-null and
+    /// suitably aligned) pointer, like [`NonNull::dangling`].
+    ///
     /// # Panics
     ///
     /// Panics if reserving space matching `layout` fails.
     #[inline(always)]
     pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
-        self.try_alloc_layout(layout).unwrap_or_else(|_| oom())
+        self.try_alloc_layout(layout).unwrap_or_else(|_| self.oom())
     }
 
     /// Attempts to allocate space for an object with the given `Layout` or else returns
@@ -1872,24 +4626,210 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// initialized with
     /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
     ///
+    /// Zero-sized layouts never touch the arena: they do not move the bump
+    /// pointer or allocate a new chunk, and get a dangling (but non-null and
+    /// suitably aligned) pointer, like [`NonNull::dangling`].
+    ///
     /// # Errors
     ///
     /// Errors if reserving space matching `layout` fails.
     #[inline(always)]
     pub fn try_alloc_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
-        if let Some(p) = self.try_alloc_layout_fast(layout) {
-            Ok(p)
-        } else {
-            self.alloc_layout_slow(layout).ok_or(AllocErr)
+        if layout.size() == 0 {
+            return Ok(dangling_aligned(layout.align().max(MIN_ALIGN)));
+        }
+
+        #[cfg(feature = "canaries")]
+        if let Some(state) = self.canaries.get() {
+            let p = self.alloc_layout_with_canary(layout, state)?;
+            #[cfg(feature = "tracking")]
+            self.record_alignment(layout);
+            return Ok(p);
+        }
+
+        let p = match self.try_alloc_layout_fast(layout) {
+            Some(p) => p,
+            None => self.alloc_layout_slow(layout).ok_or(AllocErr)?,
+        };
+        #[cfg(feature = "tracking")]
+        self.record_alignment(layout);
+        Ok(p)
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout` in
+    /// the current chunk only, returning `None` if it doesn't fit there.
+    ///
+    /// Unlike [`try_alloc_layout`](Bump::try_alloc_layout), this never
+    /// allocates a new chunk (nor does it take the allocation limit or the
+    /// [large allocation threshold](Bump::set_large_allocation_threshold)
+    /// into account), so the caller can decide what to do when the current
+    /// chunk is full, for example switch to another arena. An allocation
+    /// fits if there is enough room for it in
+    /// [`chunk_capacity`](Bump::chunk_capacity) after aligning it.
+    ///
+    /// Zero-sized layouts always succeed, and get a dangling pointer like
+    /// with [`alloc_layout`](Bump::alloc_layout). Allocations made this way
+    /// are not followed by canaries in an arena created with
+    /// `Bump::with_canaries`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::with_capacity(64);
+    /// let capacity = bump.chunk_capacity();
+    ///
+    /// let fits = Layout::from_size_align(capacity, 1).unwrap();
+    /// let too_big = Layout::from_size_align(capacity + 1, 1).unwrap();
+    /// assert!(bump.alloc_layout_in_chunk(too_big).is_none());
+    /// assert!(bump.alloc_layout_in_chunk(fits).is_some());
+    /// assert_eq!(bump.chunk_capacity(), 0);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_layout_in_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if layout.size() == 0 {
+            return Some(dangling_aligned(layout.align().max(MIN_ALIGN)));
+        }
+
+        let p = self.try_alloc_layout_fast(layout)?;
+        #[cfg(feature = "tracking")]
+        self.record_alignment(layout);
+        Some(p)
+    }
+
+    #[cfg(feature = "tracking")]
+    #[inline]
+    fn record_alignment(&self, layout: Layout) {
+        let bytes = &self.alignment_histogram[layout.align().trailing_zeros() as usize];
+        bytes.set(bytes.get() + layout.size());
+    }
+
+    /// Allocate zeroed space for an object with the given `Layout`.
+    ///
+    /// This is like [`alloc_layout`](Bump::alloc_layout), except that the
+    /// `layout.size()` bytes at the returned pointer are all zero.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let ptr = bump.alloc_layout_zeroed(Layout::new::<[u64; 4]>());
+    /// let array = unsafe { ptr.cast::<[u64; 4]>().as_ref() };
+    /// assert_eq!(array, &[0; 4]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_layout_zeroed(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_layout_zeroed(layout)
+            .unwrap_or_else(|_| self.oom())
+    }
+
+    /// Attempts to allocate zeroed space for an object with the given
+    /// `Layout` or else returns an `Err`.
+    ///
+    /// This is like [`try_alloc_layout`](Bump::try_alloc_layout), except that
+    /// the `layout.size()` bytes at the returned pointer are all zero.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(0));
+    /// assert!(bump.try_alloc_layout_zeroed(Layout::new::<u64>()).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_layout_zeroed(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let ptr = self.try_alloc_layout(layout)?;
+        unsafe {
+            ptr::write_bytes(ptr.as_ptr(), 0, layout.size());
+        }
+        Ok(ptr)
+    }
+
+    /// Allocate space for an object with the given `Layout`, and also return
+    /// how many extra bytes directly after the requested `layout.size()` bytes
+    /// belong to the allocation.
+    ///
+    /// The arena always reserves the size rounded up to a multiple of the
+    /// alignment (and of the arena's minimum alignment), so the returned
+    /// pointer is valid for `layout.size() + excess` bytes, much like
+    /// `Allocator::allocate` returning a larger slice. The excess bytes are
+    /// not used by any other allocation and can hold follow-on data.
+    ///
+    /// Note that `Bump` bump allocates *downwards*, so the rest of the current
+    /// chunk's free space lies below the returned pointer, not after the
+    /// allocation. Use [`chunk_capacity`](Bump::chunk_capacity) to query it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let (ptr, excess) = bump.alloc_layout_excess(Layout::from_size_align(5, 4).unwrap());
+    /// assert_eq!(excess, 3);
+    ///
+    /// // All eight bytes are ours to use.
+    /// unsafe { ptr.as_ptr().write_bytes(0, 5 + excess) };
+    /// ```
+    #[inline(always)]
+    pub fn alloc_layout_excess(&self, layout: Layout) -> (NonNull<u8>, usize) {
+        self.try_alloc_layout_excess(layout)
+            .unwrap_or_else(|_| self.oom())
+    }
+
+    /// Like [`alloc_layout_excess`](Bump::alloc_layout_excess), but returns
+    /// an `Err` instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn try_alloc_layout_excess(
+        &self,
+        layout: Layout,
+    ) -> Result<(NonNull<u8>, usize), AllocErr> {
+        let ptr = self.try_alloc_layout(layout)?;
+        if layout.size() == 0 {
+            return Ok((ptr, 0));
+        }
+
+        // The bytes after a guarded allocation are its canary.
+        #[cfg(feature = "canaries")]
+        if self.canaries.get().is_some() {
+            return Ok((ptr, 0));
         }
+
+        // Every allocation path reserves the size rounded up to the larger of
+        // the layout's and the arena's alignment, and that rounding cannot
+        // overflow because it fit in the arena.
+        let align = layout.align().max(MIN_ALIGN);
+        let reserved = unsafe { round_up_to_unchecked(layout.size(), align) };
+        Ok((ptr, reserved - layout.size()))
     }
 
     #[inline(always)]
     fn try_alloc_layout_fast(&self, layout: Layout) -> Option<NonNull<u8>> {
-        // We don't need to check for ZSTs here since they will automatically
-        // be handled properly: the pointer will be bumped by zero bytes,
-        // modulo alignment. This keeps the fast path optimized for non-ZSTs,
-        // which are much more common.
+        // Public allocation methods filter out ZSTs before getting here, but
+        // zero-sized layouts are still handled properly (e.g. when `grow`
+        // asks for a zero-sized delta): the pointer will be bumped by zero
+        // bytes, modulo alignment.
         unsafe {
             let footer_ptr = self.current_chunk_footer.get();
             let footer = footer_ptr.as_ref();
@@ -1908,35 +4848,32 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                 is_pointer_aligned_to(ptr, MIN_ALIGN),
                 "bump pointer {ptr:#p} should be aligned to the minimum alignment of {MIN_ALIGN:#x}"
             );
+            debug_assert!(
+                is_pointer_aligned_to(start, MIN_ALIGN),
+                "start pointer {start:#p} should be aligned to the minimum alignment of {MIN_ALIGN:#x}"
+            );
 
             // This `match` should be boiled away by LLVM: `MIN_ALIGN` is a
             // constant and the layout's alignment is also constant in practice
             // after inlining.
             let aligned_ptr = match layout.align().cmp(&MIN_ALIGN) {
-                Ordering::Less => {
-                    // We need to round the size up to a multiple of `MIN_ALIGN`
-                    // to preserve the minimum alignment. This might overflow
-                    // since we cannot rely on `Layout`'s guarantees.
-                    let aligned_size = round_up_to(layout.size(), MIN_ALIGN)?;
-
+                Ordering::Less | Ordering::Equal => {
+                    // Fast path: the bump pointer already satisfies the
+                    // requested alignment, so this is a single bounds check
+                    // and pointer decrement.
+                    //
+                    // Both `ptr` and `start` are aligned to `MIN_ALIGN`, so
+                    // `capacity` is a multiple of `MIN_ALIGN`. Therefore, if
+                    // `layout.size() <= capacity`, then rounding the size up
+                    // to a multiple of `MIN_ALIGN` (to preserve the minimum
+                    // alignment) can neither overflow nor exceed `capacity`.
                     let capacity = (ptr as usize) - (start as usize);
-                    if aligned_size > capacity {
+                    if layout.size() > capacity {
                         return None;
                     }
 
-                    ptr.wrapping_sub(aligned_size)
-                }
-                Ordering::Equal => {
-                    // `Layout` guarantees that rounding the size up to its
-                    // align cannot overflow (but does not guarantee that the
-                    // size is initially a multiple of the alignment, which is
-                    // why we need to do this rounding).
-                    let aligned_size = round_up_to_unchecked(layout.size(), layout.align());
-
-                    let capacity = (ptr as usize) - (start as usize);
-                    if aligned_size > capacity {
-                        return None;
-                    }
+                    let aligned_size = round_up_to_unchecked(layout.size(), MIN_ALIGN);
+                    debug_assert!(aligned_size <= capacity);
 
                     ptr.wrapping_sub(aligned_size)
                 }
@@ -1985,18 +4922,87 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// ## Example
     ///
     /// ```
-    /// use bumpalo::Bump;
-    ///
-    /// let bump = Bump::with_capacity(100);
+    /// use bumpalo::Bump;
+    ///
+    /// let bump = Bump::with_capacity(100);
+    ///
+    /// let capacity = bump.chunk_capacity();
+    /// assert!(capacity >= 100);
+    /// ```
+    pub fn chunk_capacity(&self) -> usize {
+        let current_footer = self.current_chunk_footer.get();
+        let current_footer = unsafe { current_footer.as_ref() };
+
+        current_footer.ptr.get().as_ptr() as usize - current_footer.data.as_ptr() as usize
+    }
+
+    /// Gets the largest alignment with which a one-byte allocation would still
+    /// fit in the current chunk, without allocating a new one.
+    ///
+    /// This depends on both the remaining capacity and the address of the bump
+    /// pointer, and lets callers decide whether to start a fresh chunk before
+    /// a highly-aligned allocation. The result is a power of two, and at least
+    /// the arena's `MIN_ALIGN`, unless the current chunk is full (or there is
+    /// no chunk yet), in which case it is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::with_capacity(4096);
+    /// let align = bump.max_satisfiable_align();
+    /// assert!(align.is_power_of_two());
+    ///
+    /// let chunks = bump.chunk_capacities().count();
+    /// bump.alloc_layout(Layout::from_size_align(1, align).unwrap());
+    /// assert_eq!(bump.chunk_capacities().count(), chunks);
+    /// ```
+    pub fn max_satisfiable_align(&self) -> usize {
+        let footer = unsafe { self.current_chunk_footer.get().as_ref() };
+        let ptr = footer.ptr.get().as_ptr() as usize;
+        let start = footer.data.as_ptr() as usize;
+        let capacity = ptr - start;
+        if capacity == 0 {
+            return 0;
+        }
+
+        // An alignment above `MIN_ALIGN` rounds the one-byte allocation's size
+        // up to the alignment, so it needs at least that many bytes. If an
+        // alignment fits, so do all smaller ones, so search downwards.
+        let mut align = 1 << (usize::BITS - 1 - capacity.leading_zeros());
+        while align > MIN_ALIGN {
+            let aligned_ptr = ptr & !(align - 1);
+            if aligned_ptr >= start && aligned_ptr - start >= align {
+                return align;
+            }
+            align /= 2;
+        }
+        MIN_ALIGN
+    }
+
+    /// Gets the number of bytes in use in the current chunk.
+    ///
+    /// This is the current chunk's entry in
+    /// [`allocated_bytes_per_chunk`](Bump::allocated_bytes_per_chunk), but
+    /// computed in constant time. Together with
+    /// [`chunk_capacity`](Bump::chunk_capacity), it describes how full the
+    /// current chunk is.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(100);
+    /// assert_eq!(bump.current_chunk_allocated_bytes(), 0);
     ///
-    /// let capacity = bump.chunk_capacity();
-    /// assert!(capacity >= 100);
+    /// bump.alloc([0_u8; 10]);
+    /// assert_eq!(bump.current_chunk_allocated_bytes(), 10);
     /// ```
-    pub fn chunk_capacity(&self) -> usize {
+    pub fn current_chunk_allocated_bytes(&self) -> usize {
         let current_footer = self.current_chunk_footer.get();
-        let current_footer = unsafe { current_footer.as_ref() };
 
-        current_footer.ptr.get().as_ptr() as usize - current_footer.data.as_ptr() as usize
+        current_footer.as_ptr() as usize
+            - unsafe { current_footer.as_ref() }.ptr.get().as_ptr() as usize
     }
 
     /// Slow path allocation for when we need to allocate a new chunk from the
@@ -2005,6 +5011,15 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     #[cold]
     fn alloc_layout_slow(&self, layout: Layout) -> Option<NonNull<u8>> {
         unsafe {
+            let is_large = self
+                .large_allocation_threshold()
+                .is_some_and(|threshold| layout.size() >= threshold);
+            if (layout.align() > MAX_SHARED_CHUNK_ALIGN || is_large)
+                && !self.current_chunk_footer.get().as_ref().is_empty()
+            {
+                return self.alloc_layout_in_dedicated_chunk(layout);
+            }
+
             let allocation_limit_remaining = self.allocation_limit_remaining();
 
             // Get a new chunk from the global allocator.
@@ -2012,14 +5027,22 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
             let current_layout = current_footer.as_ref().layout;
 
             // By default, we want our new chunk to be about twice as big
-            // as the previous chunk. If the global allocator refuses it,
-            // we try to divide it by half until it works or the requested
-            // size is smaller than the default footer size.
+            // as the previous chunk (or whatever the growth factor says). If
+            // the global allocator refuses it, we try to divide it by half
+            // until it works or the requested size is smaller than the default
+            // footer size.
             let min_new_chunk_size = layout.size().max(DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER);
-            let mut base_size = (current_layout.size() - FOOTER_SIZE)
-                .checked_mul(2)?
+            let (numerator, denominator) = self.chunk_growth_factor();
+            let prev_size = current_layout.size() - FOOTER_SIZE;
+            // Widen so that `prev_size * numerator` can't overflow, and
+            // saturate if the grown size doesn't fit in `usize`: the halving
+            // below brings it back down to something allocatable.
+            let grown = prev_size as u128 * u128::from(numerator) / u128::from(denominator);
+            let mut base_size = usize::try_from(grown)
+                .unwrap_or(usize::MAX)
+                .max(self.min_chunk_size())
                 .max(min_new_chunk_size);
-            let chunk_memory_details = iter::from_fn(|| {
+            let chunk_memory_details = iter::from_fn(|| loop {
                 let bypass_min_chunk_size_for_small_limits = matches!(self.allocation_limit(), Some(limit) if layout.size() < limit
                             && base_size >= layout.size()
                             && limit < DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER
@@ -2028,9 +5051,13 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                 if base_size >= min_new_chunk_size || bypass_min_chunk_size_for_small_limits {
                     let size = base_size;
                     base_size /= 2;
-                    Self::new_chunk_memory_details(Some(size), layout)
+                    // A size too large to even round to a chunk size is
+                    // skipped in favor of the next, halved one.
+                    if let Some(details) = Self::new_chunk_memory_details(Some(size), layout) {
+                        return Some(details);
+                    }
                 } else {
-                    None
+                    return None;
                 }
             });
 
@@ -2040,7 +5067,12 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
                         allocation_limit_remaining,
                         chunk_memory_details,
                     ) {
-                        Self::new_chunk(chunk_memory_details, layout, current_footer)
+                        Self::new_chunk(
+                            chunk_memory_details,
+                            layout,
+                            current_footer,
+                            self.chunk_source(),
+                        )
                     } else {
                         None
                     }
@@ -2053,7 +5085,12 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
             );
 
             // Set the new chunk as our new current chunk.
+            if current_footer.as_ref().is_empty() {
+                self.first_chunk_footer.set(new_footer);
+            }
             self.current_chunk_footer.set(new_footer);
+            #[cfg(feature = "tracking")]
+            self.update_peak_allocated_bytes();
 
             // And then we can rely on `tray_alloc_layout_fast` to allocate
             // space within this chunk.
@@ -2063,17 +5100,73 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
         }
     }
 
+    /// Allocate a chunk just big enough for a single over-aligned or large
+    /// allocation, and link it in *behind* the current chunk.
+    ///
+    /// Satisfying a large alignment requirement from a regular chunk would
+    /// round the bump pointer down to that alignment, wasting up to `align`
+    /// bytes and usually exhausting the chunk. Similarly, making a new chunk
+    /// current for a large allocation would waste the rest of the current
+    /// chunk. Keeping such allocations out of the way means that subsequent
+    /// allocations continue from the current chunk as if nothing had happened.
+    unsafe fn alloc_layout_in_dedicated_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let current_footer = self.current_chunk_footer.get();
+
+        // Don't add any of the usual growth on top of what this allocation
+        // needs; nothing else will be allocated in this chunk.
+        let chunk_memory_details = Self::new_chunk_memory_details(Some(0), layout)?;
+        if !Self::chunk_fits_under_limit(self.allocation_limit_remaining(), chunk_memory_details) {
+            return None;
+        }
+
+        let prev = current_footer.as_ref().prev.get();
+        let new_footer = Self::new_chunk(chunk_memory_details, layout, prev, self.chunk_source())?;
+
+        // Splice the new chunk into the list between the current chunk and
+        // its predecessor, keeping the current chunk's running total of
+        // allocated bytes correct.
+        current_footer.as_ref().prev.set(new_footer);
+        (*current_footer.as_ptr()).allocated_bytes += chunk_memory_details.new_size_without_footer;
+        #[cfg(feature = "tracking")]
+        self.update_peak_allocated_bytes();
+
+        // Carve the allocation out of the top of the new chunk, the same way
+        // the fast path would.
+        let footer = new_footer.as_ref();
+        let aligned_ptr = round_mut_ptr_down_to(footer.ptr.get().as_ptr(), layout.align());
+        let aligned_size = round_up_to_unchecked(layout.size(), layout.align());
+        debug_assert!(aligned_ptr as usize - footer.data.as_ptr() as usize >= aligned_size);
+        let ptr = NonNull::new_unchecked(aligned_ptr.sub(aligned_size));
+        footer.ptr.set(ptr);
+        Some(ptr)
+    }
+
     /// Returns an iterator over each chunk of allocated memory that
     /// this arena has bump allocated into.
     ///
     /// The chunks are returned ordered by allocation time, with the most
     /// recently allocated chunk being returned first, and the least recently
-    /// allocated chunk being returned last.
+    /// allocated chunk being returned last. The exception is chunks dedicated
+    /// to a single allocation with an alignment larger than a page (or above
+    /// the [large allocation threshold](Bump::set_large_allocation_threshold)),
+    /// which are returned right after the chunk that was current when they
+    /// were allocated.
     ///
     /// The values inside each chunk are also ordered by allocation time, with
     /// the most recent allocation being earlier in the slice, and the least
     /// recent allocation being towards the end of the slice.
     ///
+    /// Each slice spans exactly the allocated part of its chunk and never
+    /// includes the chunk's unused capacity. Allocations within it are packed
+    /// densely: the only bytes that do not belong to an allocation are the
+    /// alignment padding placed between allocations. Any code that relies on
+    /// the layout of arena contents can depend on these properties.
+    ///
+    /// The exception is an arena created with `Bump::with_canaries`: there,
+    /// each allocation is directly followed by its canary bytes and
+    /// accompanied by a record of the canary, both of which are part of the
+    /// chunk's allocated region without belonging to any allocation.
+    ///
     /// ## Safety
     ///
     /// Because this method takes `&mut self`, we know that the bump arena
@@ -2129,92 +5222,688 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
     /// // through the chunk's data, we get them in the order 'c', then 'b',
     /// // then 'a'.
     ///
-    /// bump.reset();
-    /// bump.alloc(b'a');
-    /// bump.alloc(b'b');
-    /// bump.alloc(b'c');
+    /// bump.reset();
+    /// bump.alloc(b'a');
+    /// bump.alloc(b'b');
+    /// bump.alloc(b'c');
+    ///
+    /// assert_eq!(bump.iter_allocated_chunks().count(), 1);
+    /// let chunk = bump.iter_allocated_chunks().nth(0).unwrap();
+    /// assert_eq!(chunk.len(), 3);
+    ///
+    /// // Safe because we've only allocated `u8`s in this arena, which
+    /// // fulfills the above requirements.
+    /// unsafe {
+    ///     assert_eq!(chunk[0].assume_init(), b'c');
+    ///     assert_eq!(chunk[1].assume_init(), b'b');
+    ///     assert_eq!(chunk[2].assume_init(), b'a');
+    /// }
+    /// ```
+    pub fn iter_allocated_chunks(&mut self) -> ChunkIter<'_, MIN_ALIGN> {
+        // Safety: Ensured by mutable borrow of `self`.
+        let raw = unsafe { self.iter_allocated_chunks_raw() };
+        ChunkIter {
+            raw,
+            bump: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over raw pointers to chunks of allocated memory that
+    /// this arena has bump allocated into.
+    ///
+    /// This is an unsafe version of [`iter_allocated_chunks()`](Bump::iter_allocated_chunks),
+    /// with the caller responsible for safe usage of the returned pointers as
+    /// well as ensuring that the iterator is not invalidated by new
+    /// allocations.
+    ///
+    /// ## Safety
+    ///
+    /// Allocations from this arena must not be performed while the returned
+    /// iterator is alive. If reading the chunk data (or casting to a reference)
+    /// the caller must ensure that there exist no mutable references to
+    /// previously allocated data.
+    ///
+    /// In addition, all of the caveats when reading the chunk data from
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks) still apply.
+    pub unsafe fn iter_allocated_chunks_raw(&self) -> ChunkRawIter<'_, MIN_ALIGN> {
+        ChunkRawIter {
+            footer: self.current_chunk_footer.get(),
+            bump: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over each chunk of allocated memory that this arena
+    /// has bump allocated into, from a shared reference.
+    ///
+    /// This is the same iterator as
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks), for arenas
+    /// kept behind interior mutability (such as `Rc<RefCell<Bump>>`), where
+    /// getting a `&mut Bump` would conflict with outstanding shared borrows of
+    /// allocated data.
+    ///
+    /// ## Safety
+    ///
+    /// For as long as the returned iterator or any slice it has yielded is
+    /// alive:
+    ///
+    /// * No allocation may be made from this arena, through any method.
+    ///   Allocating moves the bump pointer of the current chunk, and may link
+    ///   in a new chunk, so the iterator and the yielded slices would no longer
+    ///   describe the arena's contents.
+    ///
+    /// * The arena must not be reset, rewound (by a
+    ///   [`with_checkpoint`](Bump::with_checkpoint) scope ending), or dropped.
+    ///   These free or reuse the memory that the yielded slices point into.
+    ///
+    /// * There must be no mutable references to previously allocated data,
+    ///   since the yielded slices alias all of it. Shared references are fine.
+    ///
+    /// In addition, all of the caveats when reading the chunk data from
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks) still apply.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    ///
+    /// let bump = RefCell::new(bumpalo::Bump::new());
+    /// let shared = bump.borrow();
+    /// let x: &u8 = shared.alloc(7_u8);
+    ///
+    /// // Safe because nothing is allocated while the chunks are inspected, and
+    /// // only a shared reference to allocated data is alive.
+    /// let used: usize = unsafe { shared.iter_allocated_chunks_shared() }
+    ///     .map(|chunk| chunk.len())
+    ///     .sum();
+    /// assert_eq!(used, 1);
+    /// assert_eq!(*x, 7);
+    /// ```
+    pub unsafe fn iter_allocated_chunks_shared(&self) -> ChunkIter<'_, MIN_ALIGN> {
+        ChunkIter {
+            raw: self.iter_allocated_chunks_raw(),
+            bump: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the address range of the allocated part of
+    /// each chunk of this arena.
+    ///
+    /// Each range is `[start, end)`, where `start` is the address of the most
+    /// recent allocation in the chunk and `end` is the end of the chunk's
+    /// usable memory. The ranges are yielded in the same order as the chunks of
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks), and never
+    /// overlap. An arena that hasn't allocated yet yields no ranges.
+    ///
+    /// This is the addressing counterpart of `iter_allocated_chunks()`: it
+    /// lets tools such as conservative garbage collectors or pointer
+    /// validators tell whether an arbitrary address points into memory that
+    /// this arena has handed out, and into which chunk. Only addresses are
+    /// yielded, so this is safe to call; dereferencing them is subject to the
+    /// same rules as the pointers from
+    /// [`iter_allocated_chunks_raw()`](Bump::iter_allocated_chunks_raw).
+    ///
+    /// The ranges describe the arena at the time each chunk is visited. A
+    /// range that has been yielded doesn't grow to cover later allocations.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc(42_u64);
+    /// let addr = x as *mut u64 as usize;
+    ///
+    /// assert!(bump.allocated_ranges().any(|r| r.contains(&addr)));
+    ///
+    /// let y = 42_u64;
+    /// let addr = &y as *const u64 as usize;
+    /// assert!(!bump.allocated_ranges().any(|r| r.contains(&addr)));
+    /// ```
+    pub fn allocated_ranges(&self) -> impl Iterator<Item = core::ops::Range<usize>> + '_ {
+        // Safety: chunks can only be freed through `&mut self`, and the
+        // pointers are never dereferenced.
+        unsafe { self.iter_allocated_chunks_raw() }.map(|(ptr, len)| {
+            let start = ptr as usize;
+            start..start + len
+        })
+    }
+
+    /// Returns the index of the chunk whose allocated part contains `ptr`, or
+    /// `None` if no chunk does.
+    ///
+    /// Chunks are numbered from `0` for the last chunk yielded by
+    /// [`allocated_ranges()`](Bump::allocated_ranges), which is normally the
+    /// oldest one. An index is only valid until the next allocation, though:
+    /// chunks dedicated to a single allocation are linked in right behind the
+    /// current chunk rather than after it, which shifts the indices of the
+    /// current chunk and of any chunks dedicated since it became current (see
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks)).
+    ///
+    /// Only the allocated part of each chunk counts, so this returns `None`
+    /// for pointers into a chunk's unused space, and for the dangling pointers
+    /// of zero-sized allocations.
+    ///
+    /// This is meant for diagnostics, and takes time linear in the number of
+    /// chunks.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(64);
+    /// let first = bump.alloc(1_u32);
+    /// let second = bump.alloc_slice_fill_copy(1024, 0_u8);
+    ///
+    /// assert_eq!(bump.chunk_index_of(first), Some(0));
+    /// assert_eq!(bump.chunk_index_of(second), Some(1));
+    /// assert_eq!(bump.chunk_index_of(&2_u32), None);
+    /// ```
+    pub fn chunk_index_of<T: ?Sized>(&self, ptr: *const T) -> Option<usize> {
+        let addr = ptr as *const u8 as usize;
+        let mut chunks = 0;
+        let mut found = None;
+        for (i, range) in self.allocated_ranges().enumerate() {
+            if range.contains(&addr) {
+                found = Some(i);
+            }
+            chunks = i + 1;
+        }
+        found.map(|i| chunks - 1 - i)
+    }
+
+    /// Returns an iterator over the allocated bytes of each chunk of this
+    /// arena, as initialized byte slices.
+    ///
+    /// This yields the same regions, in the same order, as
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks): the unused
+    /// tail of a chunk is never included, and allocations are laid out
+    /// densely, most recent first, separated only by alignment padding. The
+    /// exception is an arena created with `Bump::with_canaries`, where the
+    /// canaries and their records are also found between allocations.
+    ///
+    /// ## Safety
+    ///
+    /// Allocations from this arena must not be performed while the returned
+    /// iterator or any of the slices it yields are alive, and there must be no
+    /// mutable references to previously allocated data during that time.
+    ///
+    /// Every allocated byte, including padding between allocations, must be
+    /// initialized. See [`iter_allocated_chunks()`](Bump::iter_allocated_chunks)
+    /// for when this is guaranteed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(1024);
+    /// bump.alloc_slice_copy(b"abc");
+    /// bump.alloc_slice_copy(b"de");
+    ///
+    /// // Safe because only bytes have been allocated, and no references to
+    /// // them are alive.
+    /// let regions: Vec<&[u8]> = unsafe { bump.iter_allocated_regions().collect() };
+    /// assert_eq!(regions, [&b"deabc"[..]]);
+    /// ```
+    pub unsafe fn iter_allocated_regions(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.iter_allocated_chunks_raw()
+            .map(|(ptr, len)| slice::from_raw_parts(ptr, len))
+    }
+
+    /// Calculates the number of bytes currently allocated across all chunks in
+    /// this bump arena.
+    ///
+    /// If you allocate types of different alignments or types with
+    /// larger-than-typical alignment in the same arena, some padding
+    /// bytes might get allocated in the bump arena. Note that those padding
+    /// bytes will add to this method's resulting sum, so you cannot rely
+    /// on it only counting the sum of the sizes of the things
+    /// you've allocated in the arena.
+    ///
+    /// The allocated bytes do not include the size of bumpalo's metadata,
+    /// so the amount of memory requested from the Rust allocator is higher
+    /// than the returned value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let _x = bump.alloc_slice_fill_default::<u32>(5);
+    /// let bytes = bump.allocated_bytes();
+    /// assert!(bytes >= core::mem::size_of::<u32>() * 5);
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        let footer = self.current_chunk_footer.get();
+
+        unsafe { footer.as_ref().allocated_bytes }
+    }
+
+    /// Returns the largest value that [`allocated_bytes`](Bump::allocated_bytes)
+    /// has reached since this arena was created, or since the last call to
+    /// [`reset_peak`](Bump::reset_peak).
+    ///
+    /// The peak survives [`reset`](Bump::reset), so it can be used to find how
+    /// much memory a whole batch of work needed, for capacity planning.
+    ///
+    /// Requires the `tracking` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.alloc_slice_fill_copy(10_000, 0_u8);
+    /// let peak = bump.peak_allocated_bytes();
+    /// assert_eq!(peak, bump.allocated_bytes());
+    ///
+    /// bump.reset();
+    /// assert!(bump.allocated_bytes() <= peak);
+    /// assert_eq!(bump.peak_allocated_bytes(), peak);
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn peak_allocated_bytes(&self) -> usize {
+        self.peak_allocated_bytes.get()
+    }
+
+    /// Resets the high-water mark reported by
+    /// [`peak_allocated_bytes`](Bump::peak_allocated_bytes) down to the
+    /// current [`allocated_bytes`](Bump::allocated_bytes).
+    ///
+    /// Requires the `tracking` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.alloc_slice_fill_copy(10_000, 0_u8);
+    /// bump.reset();
+    ///
+    /// bump.reset_peak();
+    /// assert_eq!(bump.peak_allocated_bytes(), bump.allocated_bytes());
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn reset_peak(&self) {
+        self.peak_allocated_bytes.set(self.allocated_bytes());
+    }
+
+    #[cfg(feature = "tracking")]
+    #[inline]
+    fn update_peak_allocated_bytes(&self) {
+        let allocated_bytes = self.allocated_bytes();
+        if allocated_bytes > self.peak_allocated_bytes.get() {
+            self.peak_allocated_bytes.set(allocated_bytes);
+        }
+    }
+
+    /// Calculates the number of bytes requested from the Rust allocator for this `Bump`.
+    ///
+    /// This number is equal to the [`allocated_bytes()`](Self::allocated_bytes) plus
+    /// the size of the bump metadata.
+    pub fn allocated_bytes_including_metadata(&self) -> usize {
+        let metadata_size =
+            unsafe { self.iter_allocated_chunks_raw().count() * mem::size_of::<ChunkFooter>() };
+        self.allocated_bytes() + metadata_size
+    }
+
+    /// Returns how many bytes have been allocated in this arena with each
+    /// alignment, keyed by alignment.
+    ///
+    /// Every allocation made through [`alloc_layout`](Bump::alloc_layout), or
+    /// any of the allocation methods built on it, adds its size to the entry
+    /// for the alignment it asked for. Zero-sized allocations and in-place
+    /// growth of the last allocation (as done by `realloc` and by growing
+    /// collections) are not counted, and neither is the padding that aligning
+    /// an allocation takes. The counts are cleared by
+    /// [`reset`](Bump::reset), and rewound along with everything else by
+    /// [`with_checkpoint`](Bump::with_checkpoint).
+    ///
+    /// This is meant for diagnostics, for example to check whether
+    /// over-aligned allocations take up a significant part of an arena.
+    ///
+    /// Requires the `tracking` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.alloc(1_u8);
+    /// bump.alloc([0_u32; 4]);
+    /// bump.alloc(2_u32);
+    ///
+    /// let histogram = bump.alignment_histogram();
+    /// assert_eq!(histogram.get(&1), Some(&1));
+    /// assert_eq!(histogram.get(&4), Some(&20));
+    ///
+    /// bump.reset();
+    /// assert!(bump.alignment_histogram().is_empty());
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn alignment_histogram(&self) -> BTreeMap<usize, usize> {
+        self.alignment_histogram
+            .iter()
+            .enumerate()
+            .filter(|(_, bytes)| bytes.get() != 0)
+            .map(|(log2, bytes)| (1 << log2, bytes.get()))
+            .collect()
+    }
+
+    /// Returns an iterator over the total capacity (in bytes) of each chunk
+    /// of memory that this arena has allocated.
+    ///
+    /// A chunk's capacity counts both its allocated and its free space, but
+    /// not bumpalo's metadata. The chunks are returned in the same order as
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks): most recently
+    /// allocated chunk first.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(100);
+    /// let capacities: Vec<usize> = bump.chunk_capacities().collect();
+    /// assert_eq!(capacities.len(), 1);
+    /// assert!(capacities[0] >= 100);
+    ///
+    /// // Force a new chunk to be allocated.
+    /// bump.alloc([0_u8; 1000]);
+    /// let capacities: Vec<usize> = bump.chunk_capacities().collect();
+    /// assert_eq!(capacities.len(), 2);
+    /// assert!(capacities[0] >= 1000);
+    /// ```
+    pub fn chunk_capacities(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chunk_footers()
+            .map(|footer| footer as *const ChunkFooter as usize - footer.data.as_ptr() as usize)
+    }
+
+    /// Returns the combined capacity of all of this arena's chunks.
+    ///
+    /// This is an alias for [`allocated_bytes`](Bump::allocated_bytes), and
+    /// always returns the same value: what that method counts is the capacity
+    /// of every chunk, which equals the sum of
+    /// [`chunk_capacities`](Bump::chunk_capacities). Like `allocated_bytes`,
+    /// it takes constant time. Unlike [`chunk_capacity`](Bump::chunk_capacity),
+    /// it counts space that is already in use as well as free space.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(100);
+    /// assert_eq!(bump.total_capacity(), bump.chunk_capacities().sum::<usize>());
+    ///
+    /// bump.alloc([0_u8; 1000]);
+    /// assert_eq!(bump.total_capacity(), bump.chunk_capacities().sum::<usize>());
+    ///
+    /// // Resetting keeps only the last chunk.
+    /// bump.reset();
+    /// assert_eq!(bump.total_capacity(), bump.chunk_capacity());
+    /// ```
+    pub fn total_capacity(&self) -> usize {
+        self.allocated_bytes()
+    }
+
+    /// Returns an iterator over the number of bytes in use in each chunk of
+    /// memory that this arena has allocated.
+    ///
+    /// Unlike [`allocated_bytes`](Bump::allocated_bytes), which counts whole
+    /// chunks, this only counts the part of each chunk that allocations have
+    /// been bump allocated into, including any alignment padding between them.
+    /// The chunks are returned in the same order as
+    /// [`chunk_capacities`](Bump::chunk_capacities), so zipping the two gives
+    /// the used and total size of every chunk.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(100);
+    /// bump.alloc([0_u8; 10]);
+    /// let used: Vec<usize> = bump.allocated_bytes_per_chunk().collect();
+    /// assert_eq!(used, [10]);
+    ///
+    /// for (used, capacity) in bump.allocated_bytes_per_chunk().zip(bump.chunk_capacities()) {
+    ///     assert!(used <= capacity);
+    /// }
+    /// ```
+    pub fn allocated_bytes_per_chunk(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chunk_footers().map(|footer| {
+            footer as *const ChunkFooter as usize - footer.ptr.get().as_ptr() as usize
+        })
+    }
+
+    /// Returns a marker for this arena's current position, to later count
+    /// the bytes allocated after it with [`bytes_since`](Bump::bytes_since).
+    ///
+    /// This is for measuring a pending group of allocations, such as a batch
+    /// being serialized before it is committed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.alloc_str("committed");
+    ///
+    /// let marker = bump.commit_point();
+    /// bump.alloc_str("pending");
+    /// assert_eq!(bump.bytes_since(marker), 7);
+    /// ```
+    pub fn commit_point(&self) -> BumpPosition {
+        let footer = self.current_chunk_footer.get();
+        let (ptr, prev) = unsafe { (footer.as_ref().ptr.get(), footer.as_ref().prev.get()) };
+        BumpPosition { footer, ptr, prev }
+    }
+
+    /// Returns the number of bytes allocated in this arena since `marker` was
+    /// taken with [`commit_point`](Bump::commit_point), across all the chunks
+    /// allocated since then.
+    ///
+    /// Like [`allocated_bytes_per_chunk`](Bump::allocated_bytes_per_chunk),
+    /// this counts the bytes that allocations were bump allocated into,
+    /// including alignment padding, and not the unused tails of chunks that
+    /// were left behind for a new one.
     ///
-    /// assert_eq!(bump.iter_allocated_chunks().count(), 1);
-    /// let chunk = bump.iter_allocated_chunks().nth(0).unwrap();
-    /// assert_eq!(chunk.len(), 3);
+    /// This takes time linear in the number of chunks allocated since
+    /// `marker`. The result is unspecified (but safe) if `marker` came from
+    /// another arena, or if this arena was reset, or rewound past `marker` at
+    /// the end of a [`with_checkpoint`](Bump::with_checkpoint) scope, since
+    /// `marker` was taken.
+    ///
+    /// ## Example
     ///
-    /// // Safe because we've only allocated `u8`s in this arena, which
-    /// // fulfills the above requirements.
-    /// unsafe {
-    ///     assert_eq!(chunk[0].assume_init(), b'c');
-    ///     assert_eq!(chunk[1].assume_init(), b'b');
-    ///     assert_eq!(chunk[2].assume_init(), b'a');
-    /// }
     /// ```
-    pub fn iter_allocated_chunks(&mut self) -> ChunkIter<'_, MIN_ALIGN> {
-        // Safety: Ensured by mutable borrow of `self`.
-        let raw = unsafe { self.iter_allocated_chunks_raw() };
-        ChunkIter {
-            raw,
-            bump: PhantomData,
+    /// let bump = bumpalo::Bump::with_capacity(64);
+    /// let marker = bump.commit_point();
+    /// bump.alloc_slice_fill_copy(1000, 0_u8);
+    /// bump.alloc_slice_fill_copy(1000, 0_u8);
+    /// assert_eq!(bump.bytes_since(marker), 2000);
+    /// ```
+    pub fn bytes_since(&self, marker: BumpPosition) -> usize {
+        let used = |footer: &ChunkFooter| {
+            footer as *const ChunkFooter as usize - footer.ptr.get().as_ptr() as usize
+        };
+        let mut bytes = 0;
+        let mut footers = self.chunk_footers();
+        for footer in &mut footers {
+            if ptr::eq(footer, marker.footer.as_ptr()) {
+                bytes += (marker.ptr.as_ptr() as usize)
+                    .saturating_sub(footer.ptr.get().as_ptr() as usize);
+                // Chunks for large or over-aligned allocations made while this
+                // chunk was current were linked in behind it.
+                bytes += footers
+                    .take_while(|spliced| !ptr::eq(*spliced, marker.prev.as_ptr()))
+                    .map(used)
+                    .sum::<usize>();
+                return bytes;
+            }
+            bytes += used(footer);
         }
+        bytes
     }
 
-    /// Returns an iterator over raw pointers to chunks of allocated memory that
-    /// this arena has bump allocated into.
+    /// Returns a one-line summary of this arena's memory use, for logging.
     ///
-    /// This is an unsafe version of [`iter_allocated_chunks()`](Bump::iter_allocated_chunks),
-    /// with the caller responsible for safe usage of the returned pointers as
-    /// well as ensuring that the iterator is not invalidated by new
-    /// allocations.
+    /// The format is
     ///
-    /// ## Safety
+    /// ```text
+    /// Bump { chunks: <count>, used: <bytes>, capacity: <bytes>, waste: <bytes> }
+    /// ```
     ///
-    /// Allocations from this arena must not be performed while the returned
-    /// iterator is alive. If reading the chunk data (or casting to a reference)
-    /// the caller must ensure that there exist no mutable references to
-    /// previously allocated data.
+    /// where `used` is the sum of
+    /// [`allocated_bytes_per_chunk`](Bump::allocated_bytes_per_chunk),
+    /// `capacity` is the sum of [`chunk_capacities`](Bump::chunk_capacities),
+    /// and `waste` is the difference between the two. Byte counts below 1 KiB
+    /// are written as e.g. `100 B`, and larger ones with one decimal place in
+    /// the largest fitting binary unit, e.g. `12.3 KiB` or `1.0 GiB`.
     ///
-    /// In addition, all of the caveats when reading the chunk data from
-    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks) still apply.
-    pub unsafe fn iter_allocated_chunks_raw(&self) -> ChunkRawIter<'_, MIN_ALIGN> {
-        ChunkRawIter {
-            footer: self.current_chunk_footer.get(),
-            bump: PhantomData,
-        }
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(
+    ///     bump.memory_report(),
+    ///     "Bump { chunks: 0, used: 0 B, capacity: 0 B, waste: 0 B }",
+    /// );
+    ///
+    /// bump.alloc([0_u8; 100]);
+    /// println!("{}", bump.memory_report());
+    /// ```
+    pub fn memory_report(&self) -> core_alloc::string::String {
+        let chunks = self.chunk_footers().count();
+        let used: usize = self.allocated_bytes_per_chunk().sum();
+        let capacity: usize = self.chunk_capacities().sum();
+        core_alloc::format!(
+            "Bump {{ chunks: {}, used: {}, capacity: {}, waste: {} }}",
+            chunks,
+            HumanBytes(used),
+            HumanBytes(capacity),
+            HumanBytes(capacity - used),
+        )
     }
 
-    /// Calculates the number of bytes currently allocated across all chunks in
-    /// this bump arena.
+    /// Returns a canonical hex dump of every allocated byte in this arena, for
+    /// debugging binary layouts.
     ///
-    /// If you allocate types of different alignments or types with
-    /// larger-than-typical alignment in the same arena, some padding
-    /// bytes might get allocated in the bump arena. Note that those padding
-    /// bytes will add to this method's resulting sum, so you cannot rely
-    /// on it only counting the sum of the sizes of the things
-    /// you've allocated in the arena.
+    /// Chunks are dumped in the same order as
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks), each introduced
+    /// by a `chunk <index> (<len> bytes)` line. Within a chunk, every line
+    /// holds the offset from the start of the chunk's allocated region, up to
+    /// 16 bytes in hex, and an ASCII gutter in which non-printable bytes are
+    /// shown as `.`, matching the output of `hexdump -C`. Since the arena
+    /// bumps downwards, the most recent allocation comes first.
     ///
-    /// The allocated bytes do not include the size of bumpalo's metadata,
-    /// so the amount of memory requested from the Rust allocator is higher
-    /// than the returned value.
+    /// The output only depends on the arena's contents, not on the addresses
+    /// of its chunks.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ## Safety
+    ///
+    /// Every allocated byte is read, so the same requirements as for
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks) apply: all
+    /// allocated memory, including any padding between allocations, must be
+    /// initialized.
     ///
     /// ## Example
     ///
     /// ```
-    /// let bump = bumpalo::Bump::new();
-    /// let _x = bump.alloc_slice_fill_default::<u32>(5);
-    /// let bytes = bump.allocated_bytes();
-    /// assert!(bytes >= core::mem::size_of::<u32>() * 5);
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.alloc_str("hello world!");
+    ///
+    /// // Safe because only bytes without padding have been allocated.
+    /// let dump = unsafe { bump.hex_dump() };
+    /// assert_eq!(
+    ///     dump,
+    ///     "chunk 0 (12 bytes)\n\
+    ///      00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 21              |hello world!|\n",
+    /// );
     /// ```
-    pub fn allocated_bytes(&self) -> usize {
-        let footer = self.current_chunk_footer.get();
+    #[cfg(feature = "std")]
+    pub unsafe fn hex_dump(&mut self) -> core_alloc::string::String {
+        use core::fmt::Write;
+
+        let mut out = core_alloc::string::String::new();
+        for (index, chunk) in self.iter_allocated_chunks().enumerate() {
+            let _ = writeln!(out, "chunk {} ({} bytes)", index, chunk.len());
+            for (line, bytes) in chunk.chunks(16).enumerate() {
+                let _ = write!(out, "{:08x}  ", line * 16);
+                for i in 0..16 {
+                    match bytes.get(i) {
+                        Some(b) => {
+                            let _ = write!(out, "{:02x} ", b.assume_init());
+                        }
+                        None => out.push_str("   "),
+                    }
+                    if i == 7 {
+                        out.push(' ');
+                    }
+                }
+                out.push_str(" |");
+                for b in bytes {
+                    let b = b.assume_init();
+                    out.push(if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    });
+                }
+                out.push_str("|\n");
+            }
+        }
+        out
+    }
 
-        unsafe { footer.as_ref().allocated_bytes }
+    /// Iterate over this arena's chunk footers, most recently allocated chunk
+    /// first, not including the canonical empty chunk.
+    fn chunk_footers(&self) -> impl Iterator<Item = &ChunkFooter> + '_ {
+        let mut footer = self.current_chunk_footer.get();
+        iter::from_fn(move || {
+            // Safe because chunks can only be deallocated through `&mut self`,
+            // so every footer in the list outlives this `&self` borrow.
+            let current = unsafe { footer.as_ref() };
+            if current.is_empty() {
+                return None;
+            }
+            footer = current.prev.get();
+            Some(current)
+        })
     }
 
-    /// Calculates the number of bytes requested from the Rust allocator for this `Bump`.
+    #[inline]
+    fn chunk_source(&self) -> Option<&dyn ChunkSource> {
+        self.config
+            .get()
+            .and_then(|c| c.chunk_source.as_deref())
+            .map(|source| source as &dyn ChunkSource)
+    }
+
+    /// Get this arena's settings, allocating them with their defaults if none
+    /// have been set yet.
+    fn config(&self) -> &ArenaConfig {
+        self.config.get_or_init(Default::default)
+    }
+
+    /// Shrink the most recent allocation, `ptr` of `old_layout`, down to
+    /// `new_size` bytes and give the freed space back to the arena.
     ///
-    /// This number is equal to the [`allocated_bytes()`](Self::allocated_bytes) plus
-    /// the size of the bump metadata.
-    pub fn allocated_bytes_including_metadata(&self) -> usize {
-        let metadata_size =
-            unsafe { self.iter_allocated_chunks_raw().count() * mem::size_of::<ChunkFooter>() };
-        self.allocated_bytes() + metadata_size
+    /// Unlike `shrink`, this always reclaims what it can, even when that means
+    /// moving the retained bytes with an overlapping copy. Returns the new
+    /// location of the allocation, or `None` if `ptr` is not the most recent
+    /// allocation (in which case nothing is changed).
+    pub(crate) unsafe fn shrink_last_allocation_in_place(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_size: usize,
+    ) -> Option<NonNull<u8>> {
+        debug_assert!(new_size <= old_layout.size());
+        if !self.is_last_allocation(ptr) {
+            return None;
+        }
+
+        let delta = round_down_to(
+            old_layout.size() - new_size,
+            old_layout.align().max(MIN_ALIGN),
+        );
+        if delta == 0 {
+            return Some(ptr);
+        }
+
+        let new_ptr = NonNull::new_unchecked(ptr.as_ptr().add(delta));
+        ptr::copy(ptr.as_ptr(), new_ptr.as_ptr(), new_size);
+        self.current_chunk_footer.get().as_ref().ptr.set(new_ptr);
+        Some(new_ptr)
     }
 
     #[inline]
@@ -2380,11 +6069,13 @@ impl<const MIN_ALIGN: usize> Bump<MIN_ALIGN> {
 /// The values inside each chunk are also ordered by allocation time, with the most
 /// recent allocation being earlier in the slice.
 ///
-/// This struct is created by the [`iter_allocated_chunks`] method on
-/// [`Bump`]. See that function for a safety description regarding reading from the returned items.
+/// This struct is created by the [`iter_allocated_chunks`] and
+/// [`iter_allocated_chunks_shared`] methods on [`Bump`]. See those functions
+/// for a safety description regarding reading from the returned items.
 ///
 /// [`Bump`]: struct.Bump.html
 /// [`iter_allocated_chunks`]: struct.Bump.html#method.iter_allocated_chunks
+/// [`iter_allocated_chunks_shared`]: struct.Bump.html#method.iter_allocated_chunks_shared
 #[derive(Debug)]
 pub struct ChunkIter<'a, const MIN_ALIGN: usize = 1> {
     raw: ChunkRawIter<'a, MIN_ALIGN>,
@@ -2439,6 +6130,401 @@ impl<const MIN_ALIGN: usize> Iterator for ChunkRawIter<'_, MIN_ALIGN> {
 
 impl<const MIN_ALIGN: usize> iter::FusedIterator for ChunkRawIter<'_, MIN_ALIGN> {}
 
+// The smallest capacity that `Bump::try_collect_slice` grows a slice to.
+const MIN_COLLECT_CAPACITY: usize = 8;
+
+// The bytes written after every allocation in an arena created with
+// `Bump::with_canaries`.
+#[cfg(feature = "canaries")]
+const CANARY: [u8; 8] = [0xca, 0xfe, 0xba, 0xbe, 0xde, 0xad, 0xbe, 0xef];
+
+// Where an arena created with `Bump::with_canaries` is up to.
+#[cfg(feature = "canaries")]
+#[derive(Clone, Copy)]
+struct CanaryState {
+    last: Option<NonNull<CanaryRecord>>,
+    count: usize,
+}
+
+// Where the canary of a single allocation is, allocated in the arena right
+// after it.
+#[cfg(feature = "canaries")]
+struct CanaryRecord {
+    canary: NonNull<u8>,
+    index: usize,
+    prev: Option<NonNull<CanaryRecord>>,
+}
+
+#[cfg(feature = "canaries")]
+fn panicking() -> bool {
+    #[cfg(feature = "std")]
+    return std::thread::panicking();
+    #[cfg(not(feature = "std"))]
+    return false;
+}
+
+// The strings interned in an arena created with `Bump::with_interner`, both
+// for lookup and in the order they were interned, so that a checkpoint can
+// forget the ones interned after it. The `'static` lifetimes are a lie: the
+// strings point into the arena, and are forgotten before they are freed.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Interner {
+    strings: std::collections::HashSet<&'static str>,
+    order: core_alloc::vec::Vec<&'static str>,
+}
+
+// The header that prefixes every allocation made with `Bump::alloc_with_drop`
+// of a type that needs dropping.
+struct DropHeader {
+    drop_value: unsafe fn(NonNull<DropHeader>),
+    prev: Option<NonNull<DropHeader>>,
+}
+
+// The header must come first so that a pointer to it is also a pointer to the
+// whole allocation.
+#[repr(C)]
+struct WithDrop<T> {
+    header: DropHeader,
+    value: T,
+}
+
+// Drop the values in the list starting at `next`, up to but excluding `stop`.
+unsafe fn run_drops(mut next: Option<NonNull<DropHeader>>, stop: Option<NonNull<DropHeader>>) {
+    while next != stop {
+        let header = next.unwrap();
+        next = header.as_ref().prev;
+        // Keep going with the rest of the list if this destructor panics.
+        let rest = Finally(move || run_drops(next, stop));
+        (header.as_ref().drop_value)(header);
+        mem::forget(rest);
+    }
+}
+
+unsafe fn drop_with_drop_value<T>(header: NonNull<DropHeader>) {
+    ptr::drop_in_place(ptr::addr_of_mut!(
+        (*header.cast::<WithDrop<T>>().as_ptr()).value
+    ));
+}
+
+// The header that prefixes every allocation made with `Bump::alloc_tagged`.
+struct TagHeader {
+    tag: u32,
+    type_id: TypeId,
+    prev: Option<NonNull<TagHeader>>,
+}
+
+// The header must come first so that a pointer to it is also a pointer to the
+// whole tagged allocation.
+#[repr(C)]
+struct Tagged<T> {
+    header: TagHeader,
+    value: T,
+}
+
+/// An iterator over the values allocated with [`Bump::alloc_tagged`] under a
+/// particular tag.
+///
+/// This struct is created by the [`Bump::iter_tagged`] method.
+pub struct TaggedIter<'a, T> {
+    next: Option<NonNull<TagHeader>>,
+    tag: u32,
+    value: PhantomData<&'a T>,
+}
+
+impl<'a, T: 'static> Iterator for TaggedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        unsafe {
+            while let Some(ptr) = self.next {
+                let header = ptr.as_ref();
+                self.next = header.prev;
+                if header.tag == self.tag && header.type_id == TypeId::of::<T>() {
+                    let tagged = ptr.cast::<Tagged<T>>();
+                    return Some(&(*tagged.as_ptr()).value);
+                }
+            }
+            None
+        }
+    }
+}
+
+impl<'a, T: 'static> iter::FusedIterator for TaggedIter<'a, T> {}
+
+impl<'a, T> core::fmt::Debug for TaggedIter<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedIter")
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
+
+/// A position in a [`Bump`] arena, returned by [`Bump::commit_point`].
+///
+/// Pass it to [`Bump::bytes_since`] to count the bytes allocated after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BumpPosition {
+    footer: NonNull<ChunkFooter>,
+    ptr: NonNull<u8>,
+    prev: NonNull<ChunkFooter>,
+}
+
+/// A handle to a value allocated with [`Bump::alloc_checked`], which knows
+/// whether the value is still alive.
+#[cfg(target_has_atomic = "64")]
+pub struct AllocHandle<T> {
+    ptr: NonNull<T>,
+    generation: u64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<T> AllocHandle<T> {
+    /// Get a shared reference to the value, if `bump` is the arena it was
+    /// allocated in and that arena has not been reset since.
+    pub fn get<'a, const MIN_ALIGN: usize>(&self, bump: &'a Bump<MIN_ALIGN>) -> Option<&'a T> {
+        if bump.generation.get() == self.generation {
+            // Safe because generations are unique to an arena and change
+            // whenever allocations are freed, so the value is still alive,
+            // and it lives until the arena's generation next changes, which
+            // requires `&mut` access to `bump`.
+            Some(unsafe { self.ptr.as_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the generation of the arena at the time the value was allocated.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<T> Clone for AllocHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<T> Copy for AllocHandle<T> {}
+
+#[cfg(target_has_atomic = "64")]
+impl<T> core::fmt::Debug for AllocHandle<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AllocHandle")
+            .field("ptr", &self.ptr)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// Get a generation that no arena has had before.
+fn fresh_generation() -> u64 {
+    #[cfg(target_has_atomic = "64")]
+    {
+        use core::sync::atomic::{AtomicU64, Ordering};
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    }
+    #[cfg(not(target_has_atomic = "64"))]
+    {
+        0
+    }
+}
+
+/// A scope for allocating in a [`Bump`] whose allocations are rewound when it
+/// ends.
+///
+/// This struct is handed out by [`Bump::with_checkpoint`] and derefs to the
+/// arena. See that method for details.
+pub struct BumpScope<'a, const MIN_ALIGN: usize = 1> {
+    bump: &'a Bump<MIN_ALIGN>,
+    // Invariant, so that two different scopes can never be swapped with each
+    // other through their `&mut` references.
+    lifetime: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+impl<'a, const MIN_ALIGN: usize> BumpScope<'a, MIN_ALIGN> {
+    /// Run `f` with a nested scope, and rewind everything allocated in it once
+    /// `f` returns.
+    ///
+    /// This borrows the outer scope mutably, so the outer scope can't be
+    /// allocated from, and its allocations can't be used, until `f` returns.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// bump.with_checkpoint(|outer| {
+    ///     outer.alloc(1_u64);
+    ///     let used = outer.allocated_bytes_per_chunk().sum::<usize>();
+    ///
+    ///     let inner_sum = outer.with_checkpoint(|inner| *inner.alloc(2_u64) + 1);
+    ///     assert_eq!(inner_sum, 3);
+    ///
+    ///     // Only the inner allocation was rewound.
+    ///     assert_eq!(outer.allocated_bytes_per_chunk().sum::<usize>(), used);
+    /// });
+    /// ```
+    pub fn with_checkpoint<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut BumpScope<'_, MIN_ALIGN>) -> R,
+    {
+        // Safety: this scope is borrowed mutably, and it is the only way to
+        // allocate into the arena, so nothing else allocates into it or holds
+        // on to its allocations until we return.
+        unsafe { self.bump.run_checkpoint(f) }
+    }
+}
+
+impl<'a, const MIN_ALIGN: usize> core::ops::Deref for BumpScope<'a, MIN_ALIGN> {
+    type Target = Bump<MIN_ALIGN>;
+
+    fn deref(&self) -> &Bump<MIN_ALIGN> {
+        self.bump
+    }
+}
+
+impl<'a, const MIN_ALIGN: usize> core::fmt::Debug for BumpScope<'a, MIN_ALIGN> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("BumpScope").field(self.bump).finish()
+    }
+}
+
+// The state of an arena when a checkpoint was taken, which it is rewound to
+// when this is dropped.
+struct Checkpoint<'a, const MIN_ALIGN: usize> {
+    bump: &'a Bump<MIN_ALIGN>,
+    footer: NonNull<ChunkFooter>,
+    ptr: NonNull<u8>,
+    prev: NonNull<ChunkFooter>,
+    allocated_bytes: usize,
+    last_tagged: Option<NonNull<TagHeader>>,
+    last_drop: Option<NonNull<DropHeader>>,
+    first_chunk_footer: NonNull<ChunkFooter>,
+    #[cfg(feature = "canaries")]
+    canaries: Option<CanaryState>,
+    #[cfg(feature = "std")]
+    interned: usize,
+    #[cfg(feature = "tracking")]
+    alignment_histogram: [usize; usize::BITS as usize],
+}
+
+impl<'a, const MIN_ALIGN: usize> Drop for Checkpoint<'a, MIN_ALIGN> {
+    fn drop(&mut self) {
+        let this = &*self;
+        // Rewind even if a destructor panics.
+        let _rewind = Finally(|| unsafe { this.rewind() });
+        unsafe {
+            self.bump.run_drops_until(self.last_drop);
+        }
+    }
+}
+
+impl<'a, const MIN_ALIGN: usize> Checkpoint<'a, MIN_ALIGN> {
+    /// Everything that dropping the checkpoint does after running the
+    /// destructors.
+    unsafe fn rewind(&self) {
+        let bump = self.bump;
+        // Free the chunks that became current since the checkpoint, along
+        // with any over-aligned allocation chunks linked in behind them.
+        let current = bump.current_chunk_footer.get();
+        if current != self.footer {
+            let mut oldest = current;
+            while oldest.as_ref().prev.get() != self.footer {
+                oldest = oldest.as_ref().prev.get();
+            }
+            oldest.as_ref().prev.set(EMPTY_CHUNK.get());
+            dealloc_chunk_list(current, bump.chunk_source());
+            bump.current_chunk_footer.set(self.footer);
+        }
+
+        let footer = self.footer.as_ref();
+        if !footer.is_empty() {
+            // Free the over-aligned allocation chunks that were linked in
+            // behind the checkpointed chunk while it was current.
+            let spliced = footer.prev.get();
+            if spliced != self.prev {
+                let mut oldest = spliced;
+                while oldest.as_ref().prev.get() != self.prev {
+                    oldest = oldest.as_ref().prev.get();
+                }
+                oldest.as_ref().prev.set(EMPTY_CHUNK.get());
+                dealloc_chunk_list(spliced, bump.chunk_source());
+                footer.prev.set(self.prev);
+            }
+
+            footer.ptr.set(self.ptr);
+            (*self.footer.as_ptr()).allocated_bytes = self.allocated_bytes;
+        }
+
+        bump.last_tagged.set(self.last_tagged);
+        bump.first_chunk_footer.set(self.first_chunk_footer);
+        bump.generation.set(fresh_generation());
+        #[cfg(feature = "canaries")]
+        bump.canaries.set(self.canaries);
+        #[cfg(feature = "std")]
+        if let Some(interner) = bump.interner.borrow_mut().as_mut() {
+            for s in interner.order.drain(self.interned..) {
+                interner.strings.remove(s);
+            }
+        }
+        #[cfg(feature = "tracking")]
+        for (bytes, &saved) in bump.alignment_histogram.iter().zip(&self.alignment_histogram) {
+            bytes.set(saved);
+        }
+    }
+}
+
+/// A handle to the reserved, but not yet initialized, space for a value being
+/// allocated with [`Bump::alloc_cyclic`].
+///
+/// The slot only exposes the address the value will live at. Reading through
+/// that address is undefined behavior until `alloc_cyclic` has returned, since
+/// the value has not been written yet. Once it has returned, the pointer is
+/// valid for as long as the arena borrow `'a`.
+pub struct CyclicSlot<'a, T> {
+    ptr: NonNull<T>,
+    bump: PhantomData<&'a T>,
+}
+
+impl<'a, T> CyclicSlot<'a, T> {
+    /// Get a raw pointer to where the value will be placed.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<'a, T> core::fmt::Debug for CyclicSlot<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CyclicSlot").field(&self.ptr).finish()
+    }
+}
+
+/// Formats a byte count for humans, as described in `Bump::memory_report`.
+struct HumanBytes(usize);
+
+impl Display for HumanBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", value, UNITS[unit])
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn oom() -> ! {
@@ -2471,13 +6557,18 @@ unsafe impl<'a, const MIN_ALIGN: usize> alloc::Alloc for &'a Bump<MIN_ALIGN> {
 
         let new_layout = layout_from_size_align(new_size, layout.align())?;
         if new_size <= old_size {
-            self.shrink(ptr, layout, new_layout)
+            Bump::<MIN_ALIGN>::shrink(self, ptr, layout, new_layout)
         } else {
-            self.grow(ptr, layout, new_layout)
+            Bump::<MIN_ALIGN>::grow(self, ptr, layout, new_layout)
         }
     }
 }
 
+/// Deallocating the most recent allocation rewinds the bump pointer, so
+/// containers that allocate and free in stack order, like a `Box` that is
+/// created and dropped in a loop, keep reusing the same memory. Deallocating
+/// anything else is a no-op, and its space is only reclaimed by
+/// [`reset`](Bump::reset).
 #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
 unsafe impl<'a, const MIN_ALIGN: usize> Allocator for &'a Bump<MIN_ALIGN> {
     #[inline]
@@ -2489,6 +6580,15 @@ unsafe impl<'a, const MIN_ALIGN: usize> Allocator for &'a Bump<MIN_ALIGN> {
             .map_err(|_| AllocError)
     }
 
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.try_alloc_layout_zeroed(layout)
+            .map(|p| unsafe {
+                NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(p.as_ptr(), layout.size()))
+            })
+            .map_err(|_| AllocError)
+    }
+
     #[inline]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
         Bump::<MIN_ALIGN>::dealloc(self, ptr, layout)
@@ -2529,8 +6629,17 @@ unsafe impl<'a, const MIN_ALIGN: usize> Allocator for &'a Bump<MIN_ALIGN> {
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        let mut ptr = self.grow(ptr, old_layout, new_layout)?;
-        ptr.as_mut()[old_layout.size()..].fill(0);
+        let ptr = self.grow(ptr, old_layout, new_layout)?;
+
+        // Zero the new tail through a raw pointer, rather than through a
+        // `&mut [u8]` of the whole block, whose old bytes need not be
+        // initialized.
+        let new_bytes = new_layout.size() - old_layout.size();
+        ptr::write_bytes(
+            ptr.cast::<u8>().as_ptr().add(old_layout.size()),
+            0,
+            new_bytes,
+        );
         Ok(ptr)
     }
 }
@@ -0,0 +1,124 @@
+//! Arenas whose chunks are allocated from a parent [`Bump`].
+//!
+//! See [`NestedBump`] for details.
+
+use crate::{Bump, ChunkSource};
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+/// A [`Bump`] arena that gets the chunks of memory it bump allocates into from
+/// a parent `Bump`, rather than from the global allocator.
+///
+/// Create one with [`Bump::new_in`] or [`Bump::with_min_align_in`]. A
+/// `NestedBump` dereferences to a `Bump`, so all of the usual allocation
+/// methods are available on it, and nested arenas can themselves be parents,
+/// which makes it possible to build trees of arenas.
+///
+/// The chunks of a nested arena are ordinary allocations in its parent. They
+/// are handed back to the parent when the nested arena frees them, on
+/// [`reset`](NestedBump::reset) or drop, but like any other deallocation in a
+/// `Bump` that only reclaims their space if they are the parent's most recent
+/// allocation. Otherwise the space is reclaimed when the parent itself is reset.
+///
+/// A nested arena must not outlive its parent's reset, since that would leave
+/// it bump allocating into memory that the parent hands out again. This is
+/// enforced by the borrow of the parent that a `NestedBump` holds: the parent
+/// can't be reset or dropped while any of its nested arenas is alive. For the
+/// same reason, a `NestedBump` only gives out shared access to its inner
+/// `Bump`, and can't be sent to another thread.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::Bump;
+///
+/// let mut parent = Bump::new();
+/// for frame in 0..3 {
+///     // Each frame gets its own arena, whose chunks live in `parent`.
+///     let mut child = Bump::new_in(&parent);
+///     let name = child.alloc_str("frame");
+///     assert_eq!(name, "frame");
+///
+///     child.reset();
+///     assert_eq!(*child.alloc(frame), frame);
+/// }
+///
+/// // Resetting the parent reclaims everything its children allocated.
+/// parent.reset();
+/// ```
+///
+/// The parent can't be reset while a nested arena is still alive:
+///
+/// ```compile_fail
+/// use bumpalo::Bump;
+///
+/// let mut parent = Bump::new();
+/// let child = Bump::new_in(&parent);
+/// let x = child.alloc(1);
+/// parent.reset();
+/// assert_eq!(*x, 1);
+/// ```
+pub struct NestedBump<'p, const MIN_ALIGN: usize = 1> {
+    bump: Bump<MIN_ALIGN>,
+    // Borrows the parent for `'p`, and keeps `NestedBump` from being `Send`,
+    // because the parent is not `Sync`.
+    parent: PhantomData<&'p Bump<1>>,
+}
+
+impl<'p, const MIN_ALIGN: usize> NestedBump<'p, MIN_ALIGN> {
+    pub(crate) fn new<const PARENT_MIN_ALIGN: usize>(parent: &'p Bump<PARENT_MIN_ALIGN>) -> Self {
+        NestedBump {
+            bump: Bump::with_min_align_and_chunk_source(ParentSource {
+                parent: NonNull::from(parent),
+            }),
+            parent: PhantomData,
+        }
+    }
+
+    /// Reset this nested arena, like [`Bump::reset`].
+    ///
+    /// The chunks that the reset frees are handed back to the parent arena.
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+}
+
+impl<'p, const MIN_ALIGN: usize> Deref for NestedBump<'p, MIN_ALIGN> {
+    type Target = Bump<MIN_ALIGN>;
+
+    #[inline]
+    fn deref(&self) -> &Bump<MIN_ALIGN> {
+        &self.bump
+    }
+}
+
+impl<'p, const MIN_ALIGN: usize> fmt::Debug for NestedBump<'p, MIN_ALIGN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NestedBump").field(&self.bump).finish()
+    }
+}
+
+/// The chunk source of a nested arena: its parent arena.
+struct ParentSource<const MIN_ALIGN: usize> {
+    parent: NonNull<Bump<MIN_ALIGN>>,
+}
+
+// Safety: a `ParentSource` is only ever owned by the `Bump` inside a
+// `NestedBump`, which is not `Send` and never gives that `Bump` away, so the
+// parent is only used from the thread that borrowed it.
+unsafe impl<const MIN_ALIGN: usize> Send for ParentSource<MIN_ALIGN> {}
+
+unsafe impl<const MIN_ALIGN: usize> ChunkSource for ParentSource<MIN_ALIGN> {
+    fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // Safety: the parent is borrowed for as long as the nested arena lives.
+        let parent = unsafe { self.parent.as_ref() };
+        parent.try_alloc_layout(layout).ok()
+    }
+
+    unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.parent.as_ref().dealloc(ptr, layout);
+    }
+}
@@ -0,0 +1,405 @@
+//! Single-threaded reference-counting pointers for bump allocation.
+//!
+//! [`Rc<'a, T>`] provides shared ownership of a value of type `T`, allocated
+//! in a [`Bump`] arena. Cloning an [`Rc`] produces a new pointer to the same
+//! allocation and increments a reference count stored alongside the value.
+//! When the last [`Rc`] pointer to a given allocation is dropped, the value's
+//! [`Drop`] implementation is run.
+//!
+//! As with everything else allocated in a [`Bump`], the *memory* backing the
+//! value is not returned to the arena until it is reset or dropped. Only the
+//! destructor of `T` is run when the count reaches zero.
+//!
+//! [`Weak<'a, T>`] is a non-owning pointer to an [`Rc`] allocation that can be
+//! [upgraded][Weak::upgrade] back to an [`Rc`] while the value is still alive.
+//! Because the memory itself outlives every [`Weak`] pointer (it lives as long
+//! as the `'a` borrow of the arena), no separate weak count is needed.
+//!
+//! Like [`Bump`] itself, neither [`Rc`] nor [`Weak`] are `Send` or `Sync`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bumpalo::{Bump, rc::Rc};
+//!
+//! let bump = Bump::new();
+//!
+//! let five = Rc::new_in(5, &bump);
+//! let also_five = Rc::clone(&five);
+//!
+//! assert_eq!(*also_five, 5);
+//! assert_eq!(Rc::strong_count(&five), 2);
+//! assert!(Rc::ptr_eq(&five, &also_five));
+//! ```
+//!
+//! Sharing nodes between several parents, building a DAG:
+//!
+//! ```
+//! use bumpalo::{Bump, rc::Rc};
+//!
+//! struct Node<'a> {
+//!     name: &'static str,
+//!     children: Vec<Rc<'a, Node<'a>>>,
+//! }
+//!
+//! let bump = Bump::new();
+//!
+//! let leaf = Rc::new_in(Node { name: "leaf", children: vec![] }, &bump);
+//! let left = Rc::new_in(Node { name: "left", children: vec![leaf.clone()] }, &bump);
+//! let right = Rc::new_in(Node { name: "right", children: vec![leaf.clone()] }, &bump);
+//!
+//! assert_eq!(Rc::strong_count(&leaf), 3);
+//! drop((left, right));
+//! assert_eq!(Rc::strong_count(&leaf), 1);
+//! assert_eq!(leaf.name, "leaf");
+//! ```
+//!
+//! [`Bump`]: ../struct.Bump.html
+//! [`Drop`]: https://doc.rust-lang.org/std/ops/trait.Drop.html
+
+use crate::Bump;
+use core::borrow;
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+
+// The allocation shared by all `Rc`s and `Weak`s pointing at one value.
+struct RcBox<T> {
+    // The number of live `Rc`s. The value is dropped when this reaches zero,
+    // and `Weak::upgrade` fails from then on.
+    strong: Cell<usize>,
+    value: ManuallyDrop<T>,
+}
+
+/// A single-threaded reference-counting pointer to a bump-allocated `T`
+/// value, that runs `T`'s `Drop` implementation when the last strong
+/// reference is dropped.
+///
+/// See the [module-level documentation][crate::rc] for more details.
+pub struct Rc<'a, T> {
+    ptr: NonNull<RcBox<T>>,
+    // Borrows the arena for `'a`, and owns a `T` for the purposes of dropck.
+    phantom: PhantomData<(&'a Bump, T)>,
+}
+
+/// A non-owning pointer to a value managed by an [`Rc`].
+///
+/// Use [`Weak::upgrade`] to get an [`Rc`] to the value, which succeeds only if
+/// the value has not been dropped yet.
+pub struct Weak<'a, T> {
+    ptr: NonNull<RcBox<T>>,
+    phantom: PhantomData<&'a Bump>,
+}
+
+#[cold]
+#[inline(never)]
+fn strong_count_overflow() -> ! {
+    panic!("reference count overflowed")
+}
+
+impl<'a, T> Rc<'a, T> {
+    /// Allocates a reference-counted `value` inside the given `Bump`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let five = Rc::new_in(5, &b);
+    /// ```
+    #[inline]
+    pub fn new_in(value: T, bump: &'a Bump) -> Rc<'a, T> {
+        let inner = bump.alloc(RcBox {
+            strong: Cell::new(1),
+            value: ManuallyDrop::new(value),
+        });
+        Rc {
+            ptr: NonNull::from(inner),
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn inner(&self) -> &RcBox<T> {
+        // The `RcBox` lives for as long as the arena borrow `'a`, which
+        // outlives `self`.
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Gets the number of [`Rc`] pointers to this allocation.
+    ///
+    /// Note: this is an associated function, which means that you have to call
+    /// it as `Rc::strong_count(&r)` instead of `r.strong_count()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let five = Rc::new_in(5, &b);
+    /// let _also_five = Rc::clone(&five);
+    ///
+    /// assert_eq!(Rc::strong_count(&five), 2);
+    /// ```
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().strong.get()
+    }
+
+    /// Creates a new [`Weak`] pointer to this allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let five = Rc::new_in(5, &b);
+    /// let weak_five = Rc::downgrade(&five);
+    ///
+    /// assert_eq!(weak_five.upgrade().as_deref(), Some(&5));
+    /// drop(five);
+    /// assert!(weak_five.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn downgrade(this: &Self) -> Weak<'a, T> {
+        Weak {
+            ptr: this.ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the two `Rc`s point to the same allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let five = Rc::new_in(5, &b);
+    /// let same_five = Rc::clone(&five);
+    /// let other_five = Rc::new_in(5, &b);
+    ///
+    /// assert!(Rc::ptr_eq(&five, &same_five));
+    /// assert!(!Rc::ptr_eq(&five, &other_five));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+
+    /// Returns the inner value, if the `Rc` has exactly one strong reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same `Rc` that was passed
+    /// in. Any outstanding [`Weak`] pointers will fail to upgrade afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let x = Rc::new_in(3, &b);
+    /// assert_eq!(Rc::try_unwrap(x), Ok(3));
+    ///
+    /// let x = Rc::new_in(4, &b);
+    /// let _y = Rc::clone(&x);
+    /// assert_eq!(*Rc::try_unwrap(x).unwrap_err(), 4);
+    /// ```
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        if Rc::strong_count(&this) != 1 {
+            return Err(this);
+        }
+
+        let this = ManuallyDrop::new(this);
+        let inner = this.inner();
+        inner.strong.set(0);
+        // We were the only strong reference, and we've marked the value as
+        // dead so that neither `Drop` nor `Weak::upgrade` will touch it again.
+        Ok(unsafe { ptr::read(&*inner.value) })
+    }
+}
+
+impl<'a, T> Clone for Rc<'a, T> {
+    /// Makes a clone of the `Rc` pointer, incrementing the strong reference
+    /// count.
+    #[inline]
+    fn clone(&self) -> Self {
+        let strong = &self.inner().strong;
+        strong.set(
+            strong
+                .get()
+                .checked_add(1)
+                .unwrap_or_else(|| strong_count_overflow()),
+        );
+        Rc {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Drop for Rc<'a, T> {
+    fn drop(&mut self) {
+        let strong = &self.inner().strong;
+        strong.set(strong.get() - 1);
+        if strong.get() == 0 {
+            // `Rc` owns the value of `T`, but not the memory behind it. We
+            // were the last strong reference, so nothing else can be borrowing
+            // the value.
+            unsafe {
+                ManuallyDrop::drop(&mut *ptr::addr_of_mut!((*self.ptr.as_ptr()).value));
+            }
+        }
+    }
+}
+
+impl<'a, T> Deref for Rc<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<'a, T> borrow::Borrow<T> for Rc<'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> AsRef<T> for Rc<'a, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<Rc<'b, T>> for Rc<'a, T> {
+    #[inline]
+    fn eq(&self, other: &Rc<'b, T>) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<'a, T: Eq> Eq for Rc<'a, T> {}
+
+impl<'a, 'b, T: PartialOrd> PartialOrd<Rc<'b, T>> for Rc<'a, T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Rc<'b, T>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: Ord> Ord for Rc<'a, T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: Hash> Hash for Rc<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for Rc<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Rc<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, T> fmt::Pointer for Rc<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ptr: *const T = &**self;
+        fmt::Pointer::fmt(&ptr, f)
+    }
+}
+
+impl<'a, T> Weak<'a, T> {
+    /// Attempts to upgrade the `Weak` pointer to an [`Rc`].
+    ///
+    /// Returns [`None`] if the inner value has since been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, rc::Rc};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let five = Rc::new_in(5, &b);
+    /// let weak_five = Rc::downgrade(&five);
+    ///
+    /// let strong_five = weak_five.upgrade();
+    /// assert!(strong_five.is_some());
+    ///
+    /// drop(strong_five);
+    /// drop(five);
+    ///
+    /// assert!(weak_five.upgrade().is_none());
+    /// ```
+    pub fn upgrade(&self) -> Option<Rc<'a, T>> {
+        // The `RcBox` memory stays valid for `'a` even after the value has
+        // been dropped.
+        let strong = unsafe { &self.ptr.as_ref().strong };
+        if strong.get() == 0 {
+            return None;
+        }
+
+        let rc = ManuallyDrop::new(Rc {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        });
+        Some(Rc::clone(&rc))
+    }
+
+    /// Gets the number of strong ([`Rc`]) pointers to this allocation.
+    ///
+    /// Returns `0` once the value has been dropped.
+    pub fn strong_count(&self) -> usize {
+        unsafe { self.ptr.as_ref().strong.get() }
+    }
+
+    /// Returns `true` if the two `Weak`s point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<'a, T> Clone for Weak<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Weak {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Weak<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
@@ -0,0 +1,278 @@
+//! A `Sync` arena made of several independent [`Bump`] shards.
+//!
+//! See [`ShardedBump`] for details.
+
+use crate::{AllocErr, Bump, ChunkIter};
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+use core_alloc::boxed::Box;
+use core_alloc::vec::Vec;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A bump arena that can be allocated from by many threads at once.
+///
+/// A `ShardedBump` holds a fixed number of [`Bump`] shards. The first time a
+/// thread allocates from a `ShardedBump`, it claims one of the shards for
+/// itself, and all of its allocations are then served from that shard. No
+/// other thread ever touches a claimed shard, so allocating takes no locks:
+/// after the first allocation, it only costs an atomic load on top of a
+/// plain `Bump` allocation.
+///
+/// A thread keeps its shard until the `ShardedBump` is
+/// [reset][ShardedBump::reset], even after the thread exits. So at most
+/// [`shards()`](ShardedBump::shards) different threads can allocate between
+/// resets, and allocating from any further thread fails.
+///
+/// Like with [`Bump`], references returned by the allocation methods live
+/// until the `ShardedBump` is [reset][ShardedBump::reset] or dropped, and
+/// `Drop` implementations of allocated values are never run.
+///
+/// Requires the `std` feature.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::ShardedBump;
+///
+/// let arena = ShardedBump::new(4);
+/// let total: u64 = std::thread::scope(|s| {
+///     let handles: Vec<_> = (0..4_u64)
+///         .map(|i| {
+///             let arena = &arena;
+///             s.spawn(move || *arena.alloc(i * 10))
+///         })
+///         .collect();
+///     handles.into_iter().map(|h| h.join().unwrap()).sum()
+/// });
+/// assert_eq!(total, 60);
+/// ```
+pub struct ShardedBump {
+    shards: Box<[Shard]>,
+}
+
+struct Shard {
+    // The `thread_id` of the thread that claimed this shard, or zero if no
+    // thread has claimed it yet.
+    owner: AtomicUsize,
+    bump: UnsafeCell<Bump>,
+}
+
+// Safety: a shard's `Bump` is only used by the thread that claimed it, or
+// through `&mut ShardedBump`.
+unsafe impl Sync for ShardedBump {}
+
+impl fmt::Debug for ShardedBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedBump")
+            .field("shards", &self.shards.len())
+            .finish()
+    }
+}
+
+/// A non-zero id that is unique to the calling thread, used to claim shards
+/// and to pick the first shard to try.
+fn thread_id() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(1);
+    std::thread_local! {
+        static ID: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    ID.with(|id| *id)
+}
+
+impl ShardedBump {
+    /// Construct a new `ShardedBump` with `shards` empty shards.
+    ///
+    /// Shards do not allocate any memory until they are first allocated from.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let arena = bumpalo::ShardedBump::new(8);
+    /// assert_eq!(arena.shards(), 8);
+    /// ```
+    pub fn new(shards: usize) -> ShardedBump {
+        assert!(shards > 0, "a ShardedBump needs at least one shard");
+        ShardedBump {
+            shards: (0..shards)
+                .map(|_| Shard {
+                    owner: AtomicUsize::new(0),
+                    bump: UnsafeCell::new(Bump::new()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Get the number of shards in this arena.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Get the calling thread's shard, claiming one if it doesn't have one
+    /// yet, or `None` if every shard belongs to another thread.
+    ///
+    /// The returned `Bump` must not be handed to another thread.
+    fn shard(&self) -> Option<&Bump> {
+        let me = thread_id();
+        let home = me % self.shards.len();
+        // Shards are tried in the same order every time, and are only
+        // released all at once, by `reset`. So the first shard in this order
+        // that isn't another thread's is either the one we claimed before, or
+        // the free one that we claim now.
+        for i in 0..self.shards.len() {
+            let shard = &self.shards[(home + i) % self.shards.len()];
+            let owner = shard.owner.load(Ordering::Relaxed);
+            if owner == me
+                || (owner == 0
+                    && shard
+                        .owner
+                        .compare_exchange(0, me, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok())
+            {
+                // Safety: this shard is ours, so no other thread uses it.
+                return Some(unsafe { &*shard.bump.get() });
+            }
+        }
+        None
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn no_shard(&self) -> ! {
+        panic!(
+            "all {} shards of this ShardedBump are claimed by other threads",
+            self.shards.len()
+        )
+    }
+
+    /// Allocate an object in this arena and return an exclusive reference to
+    /// it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails, or if every shard has been
+    /// claimed by another thread.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let arena = bumpalo::ShardedBump::new(2);
+    /// let x = arena.alloc("hello");
+    /// assert_eq!(*x, "hello");
+    /// ```
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T>(&self, val: T) -> &mut T {
+        let shard = self.shard().unwrap_or_else(|| self.no_shard());
+        let ptr: *mut T = shard.alloc(val);
+        // Safety: the allocation lives until `self` is reset or dropped, both
+        // of which require the shared borrow of `self` to have ended.
+        unsafe { &mut *ptr }
+    }
+
+    /// Try to allocate an object in this arena.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails, or if every shard has been
+    /// claimed by another thread.
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc<T>(&self, val: T) -> Result<&mut T, AllocErr> {
+        let ptr: *mut T = self.shard().ok_or(AllocErr)?.try_alloc(val)?;
+        // Safety: as in `alloc`.
+        unsafe { Ok(&mut *ptr) }
+    }
+
+    /// Copy a slice into this arena and return an exclusive reference to the
+    /// copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails, or if every shard has
+    /// been claimed by another thread.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, src: &[T]) -> &mut [T] {
+        let shard = self.shard().unwrap_or_else(|| self.no_shard());
+        let ptr: *mut [T] = shard.alloc_slice_copy(src);
+        // Safety: as in `alloc`.
+        unsafe { &mut *ptr }
+    }
+
+    /// Copy a string slice into this arena and return an exclusive reference
+    /// to the copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the string fails, or if every shard has
+    /// been claimed by another thread.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, src: &str) -> &mut str {
+        let shard = self.shard().unwrap_or_else(|| self.no_shard());
+        let ptr: *mut str = shard.alloc_str(src);
+        // Safety: as in `alloc`.
+        unsafe { &mut *ptr }
+    }
+
+    /// Allocate space for an object with the given `Layout`.
+    ///
+    /// The returned pointer points at uninitialized memory.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space matching `layout` fails, or if every shard
+    /// has been claimed by another thread.
+    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        let shard = self.shard().unwrap_or_else(|| self.no_shard());
+        shard.alloc_layout(layout)
+    }
+
+    /// Get the number of bytes allocated across all shards.
+    ///
+    /// See [`Bump::allocated_bytes`] for what is counted. This takes `&mut
+    /// self` since the shards belong to the threads that claimed them while
+    /// the arena is shared.
+    pub fn allocated_bytes(&mut self) -> usize {
+        self.shards
+            .iter_mut()
+            .map(|shard| shard.bump.get_mut().allocated_bytes())
+            .sum()
+    }
+
+    /// Reset every shard, making all of their memory available for new
+    /// allocations, and release the threads' claims on them.
+    ///
+    /// See [`Bump::reset`].
+    pub fn reset(&mut self) {
+        for shard in self.shards.iter_mut() {
+            shard.bump.get_mut().reset();
+            *shard.owner.get_mut() = 0;
+        }
+    }
+
+    /// Iterate over the allocated chunks of every shard, one shard after
+    /// another.
+    ///
+    /// Within a shard, chunks are yielded in the same order as
+    /// [`Bump::iter_allocated_chunks`].
+    pub fn iter_allocated_chunks(&mut self) -> impl Iterator<Item = &[MaybeUninit<u8>]> + '_ {
+        self.shards
+            .iter_mut()
+            .flat_map(|shard| -> ChunkIter<'_> { shard.bump.get_mut().iter_allocated_chunks() })
+    }
+
+    /// Consume this arena, returning its shards.
+    ///
+    /// Allocations stay owned by the shard they were made in.
+    pub fn into_shards(self) -> Vec<Bump> {
+        self.shards
+            .into_vec()
+            .into_iter()
+            .map(|shard| shard.bump.into_inner())
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use bumpalo::Bump;
+use std::cell::Cell;
+
+struct Node {
+    this: *const Node,
+    next: Cell<*const Node>,
+    value: u32,
+}
+
+#[test]
+fn alloc_cyclic_knows_own_address() {
+    let b = Bump::new();
+    let node = b.alloc_cyclic(|slot| Node {
+        this: slot.as_ptr(),
+        next: Cell::new(slot.as_ptr()),
+        value: 7,
+    });
+    assert_eq!(node.this, node as *const Node);
+    assert_eq!(node.next.get(), node.this);
+    assert_eq!(unsafe { (*node.this).value }, 7);
+}
+
+#[test]
+fn alloc_cyclic_ring() {
+    let b = Bump::new();
+    let first: &Node = b.alloc_cyclic(|slot| Node {
+        this: slot.as_ptr(),
+        next: Cell::new(slot.as_ptr()),
+        value: 0,
+    });
+
+    let mut last = first;
+    for value in 1..10 {
+        let node: &Node = b.alloc_cyclic(|slot| Node {
+            this: slot.as_ptr(),
+            next: Cell::new(first),
+            value,
+        });
+        last.next.set(node);
+        last = node;
+    }
+
+    let mut node = first;
+    for expected in 0..20 {
+        assert_eq!(node.value, expected % 10);
+        node = unsafe { &*node.next.get() };
+    }
+}
+
+#[test]
+fn try_alloc_cyclic_does_not_call_closure_on_failure() {
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    let res = b.try_alloc_cyclic(|_slot| -> [u8; 100] { panic!("should not be called") });
+    assert!(res.is_err());
+}
@@ -1,9 +1,17 @@
 use bumpalo::Bump;
 use std::alloc::Layout;
-use std::cmp;
+use std::cell::Cell;
 use std::iter::repeat;
 use std::mem;
 
+struct CountDrops<'a>(&'a Cell<usize>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
 #[test]
 fn alloc_slice_fill_zero() {
     let b = Bump::new();
@@ -20,21 +28,51 @@ fn alloc_slice_fill_zero() {
     b.alloc_slice_fill_clone(0, &"hello".to_string());
     b.alloc_slice_fill_default::<String>(0);
     let ptr2 = b.alloc(MyZeroSizedType);
-    let alignment = cmp::max(mem::align_of::<u64>(), mem::align_of::<String>());
-    assert_eq!(
-        ptr1.as_ptr() as usize & !(alignment - 1),
-        ptr2 as *mut _ as usize
-    );
+    // Zero-sized allocations get a dangling, aligned pointer.
+    assert_eq!(ptr2 as *mut _ as usize, b.min_align());
 
+    // None of the zero-sized allocations above moved the bump pointer, not
+    // even to align it for `u64` or `String`.
     let ptr3 = b.alloc_layout(u8_layout);
-    dbg!(ptr2 as *mut _);
+    dbg!(ptr1);
     dbg!(ptr3);
     assert_eq!(
-        ptr2 as *mut _ as usize,
+        ptr1.as_ptr() as usize,
         (ptr3.as_ptr() as usize) + b.min_align().max(u8_layout.align()),
     );
 }
 
+#[test]
+fn zero_sized_allocations_do_not_use_the_arena() {
+    #[repr(align(64))]
+    struct OverAligned;
+
+    #[cfg(not(miri))]
+    const COUNT: usize = 1_000_000;
+
+    #[cfg(miri)] // Miri is very slow, pick a smaller count that runs in a reasonable amount of time
+    const COUNT: usize = 1_000;
+
+    let mut b = Bump::new();
+    for _ in 0..COUNT {
+        let unit = b.alloc(());
+        assert_eq!(unit as *mut () as usize, 1);
+        let over_aligned = b.alloc(OverAligned);
+        assert_eq!(over_aligned as *mut OverAligned as usize, 64);
+    }
+    assert_eq!(b.allocated_bytes(), 0);
+    assert_eq!(b.iter_allocated_chunks().count(), 0);
+
+    let mut b = Bump::with_capacity(100);
+    let capacity = b.chunk_capacity();
+    for _ in 0..COUNT {
+        b.alloc(OverAligned);
+        b.alloc_layout(Layout::from_size_align(0, 4096).unwrap());
+    }
+    assert_eq!(b.chunk_capacity(), capacity);
+    assert_eq!(b.iter_allocated_chunks().count(), 1);
+}
+
 #[test]
 fn alloc_slice_try_fill_with_succeeds() {
     let b = Bump::new();
@@ -73,3 +111,502 @@ fn alloc_slice_overflow() {
 
     b.alloc_slice_fill_default::<u64>(usize::max_value());
 }
+
+#[test]
+fn alloc_slice_repeat() {
+    let b = Bump::new();
+    assert_eq!(b.alloc_slice_repeat(&[1u8, 2, 3], 1), &[1, 2, 3]);
+    assert_eq!(b.alloc_slice_repeat(&[7u32], 5), &[7, 7, 7, 7, 7]);
+
+    let expected = [1u16, 2, 3].repeat(37);
+    assert_eq!(b.alloc_slice_repeat(&[1u16, 2, 3], 37), &expected[..]);
+}
+
+#[test]
+fn alloc_slice_repeat_empty() {
+    let b = Bump::new();
+    assert!(b.alloc_slice_repeat::<u64>(&[], 100).is_empty());
+    assert!(b.alloc_slice_repeat(&[1u64, 2], 0).is_empty());
+}
+
+#[test]
+fn try_alloc_slice_repeat_overflow() {
+    let b = Bump::new();
+    assert!(b.try_alloc_slice_repeat(&[1u8, 2], usize::MAX).is_err());
+    assert!(b.try_alloc_slice_repeat(&[1u64], usize::MAX / 4).is_err());
+}
+
+#[test]
+#[should_panic(expected = "out of memory")]
+fn alloc_slice_repeat_overflow() {
+    let b = Bump::new();
+    b.alloc_slice_repeat(&[1u8, 2], usize::MAX);
+}
+
+#[test]
+fn alloc_slice_fill_while_stops_early() {
+    let b = Bump::new();
+    let x = b.alloc_slice_fill_while(10, |i| if i < 4 { Some(i * 2) } else { None });
+    assert_eq!(x, &[0, 2, 4, 6]);
+
+    let y = b.alloc_slice_fill_while(3, |i| Some(i as u8));
+    assert_eq!(y, &[0, 1, 2]);
+
+    let z = b.alloc_slice_fill_while::<String, _>(5, |_| None);
+    assert!(z.is_empty());
+}
+
+#[test]
+fn alloc_slice_fill_while_drops_initialized_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_fill_while(10, |i| {
+            if i == 4 {
+                panic!("fifth element");
+            }
+            Some(CountDrops(&drops))
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn alloc_slice_fill_while_reclaims_unused_tail() {
+    let b = Bump::with_capacity(1024);
+    let before = b.chunk_capacity();
+    let x = b.alloc_slice_fill_while(100, |i| if i < 5 { Some(i as u64) } else { None });
+    assert_eq!(x, &[0, 1, 2, 3, 4]);
+    assert_eq!(before - b.chunk_capacity(), 5 * mem::size_of::<u64>());
+}
+
+#[test]
+fn alloc_slice_fill_while_keeps_tail_after_inner_allocation() {
+    let b = Bump::with_capacity(1024);
+    let before = b.chunk_capacity();
+    let mut inner = None;
+    let x = b.alloc_slice_fill_while(10, |i| {
+        if i == 0 {
+            inner = Some(b.alloc(99u64));
+        }
+        if i < 2 {
+            Some(i as u64)
+        } else {
+            None
+        }
+    });
+    assert_eq!(x, &[0, 1]);
+    assert_eq!(*inner.unwrap(), 99);
+    assert_eq!(before - b.chunk_capacity(), 11 * mem::size_of::<u64>());
+}
+
+#[test]
+fn alloc_slice_fill_iter_n_tiles_iterator() {
+    let b = Bump::new();
+    let mut iter = (0..10_u32).map(|i| i.to_string());
+
+    let first = b.alloc_slice_fill_iter_n(&mut iter, 4);
+    assert_eq!(first, ["0", "1", "2", "3"]);
+    let second = b.alloc_slice_fill_iter_n(&mut iter, 4);
+    assert_eq!(second, ["4", "5", "6", "7"]);
+    let third = b.alloc_slice_fill_iter_n(&mut iter, 4);
+    assert_eq!(third, ["8", "9"]);
+    let fourth = b.alloc_slice_fill_iter_n(&mut iter, 4);
+    assert!(fourth.is_empty());
+
+    // Taking from an unbounded iterator never pulls more than asked for.
+    let mut naturals = 0_u64..;
+    assert_eq!(b.alloc_slice_fill_iter_n(&mut naturals, 3), &[0, 1, 2]);
+    assert_eq!(naturals.next(), Some(3));
+}
+
+#[test]
+fn alloc_slice_fill_iter_n_drops_taken_items_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let mut iter = (0..10).map(|i| {
+        if i == 3 {
+            panic!("fourth item");
+        }
+        CountDrops(&drops)
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_fill_iter_n(&mut iter, 5);
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn reserve_slice_uninit_is_aligned() {
+    let b = Bump::<16>::with_min_align();
+    b.alloc(1_u8);
+    let uninit = b.reserve_slice_uninit::<u16>(3);
+    assert_eq!(uninit.len(), 3);
+    assert_eq!(uninit.as_ptr() as usize % 16, 0);
+
+    let b = Bump::new();
+    b.alloc(1_u8);
+    let capacity = b.chunk_capacity();
+    let uninit = b.reserve_slice_uninit::<u64>(3);
+    assert_eq!(uninit.as_ptr() as usize % mem::align_of::<u64>(), 0);
+    assert!(capacity - b.chunk_capacity() >= 3 * mem::size_of::<u64>());
+}
+
+#[test]
+fn reserve_slice_uninit_overflow() {
+    let b = Bump::new();
+    assert!(b.try_reserve_slice_uninit::<u64>(usize::MAX).is_err());
+}
+
+#[test]
+fn try_alloc_slice_fill_default_alloc_failure() {
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_slice_fill_default::<u64>(1000).is_err());
+    assert_eq!(b.allocated_bytes(), 0);
+}
+
+#[test]
+fn try_alloc_slice_fill_default_drops_initialized_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    thread_local! {
+        static CREATED: Cell<usize> = const { Cell::new(0) };
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct PanicsOnFourth;
+
+    impl Default for PanicsOnFourth {
+        fn default() -> Self {
+            let created = CREATED.with(|c| c.get());
+            if created == 3 {
+                panic!("fourth default");
+            }
+            CREATED.with(|c| c.set(created + 1));
+            PanicsOnFourth
+        }
+    }
+
+    impl Drop for PanicsOnFourth {
+        fn drop(&mut self) {
+            DROPPED.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _ = b.try_alloc_slice_fill_default::<PanicsOnFourth>(10);
+    }));
+    assert!(result.is_err());
+    assert_eq!(CREATED.with(|c| c.get()), 3);
+    assert_eq!(DROPPED.with(|d| d.get()), 3);
+}
+
+#[test]
+fn alloc_slice_fill_fold_running_sum() {
+    let b = Bump::new();
+    let x = b.alloc_slice_fill_fold(5, 0_u32, |sum, i| {
+        *sum += i as u32 + 1;
+        *sum
+    });
+    assert_eq!(x, &[1, 3, 6, 10, 15]);
+
+    let empty: &mut [u32] = b.alloc_slice_fill_fold(0, (), |_, _| panic!("should not be called"));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn alloc_slice_fill_fold_drops_initialized_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_fill_fold(10, 0, |n, _| {
+            if *n == 4 {
+                panic!("fifth element");
+            }
+            *n += 1;
+            CountDrops(&drops)
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn try_collect_slice_unknown_length() {
+    let b = Bump::new();
+    let x = b
+        .try_collect_slice((0..100).filter(|n| n % 3 == 0))
+        .unwrap();
+    assert!(x.iter().copied().eq((0..100).step_by(3)));
+
+    let empty = b.try_collect_slice(std::iter::empty::<String>()).unwrap();
+    assert!(empty.is_empty());
+
+    let zsts = b.try_collect_slice((0..10).map(|_| ())).unwrap();
+    assert_eq!(zsts.len(), 10);
+}
+
+#[test]
+fn try_collect_slice_gives_back_excess_capacity() {
+    let b = Bump::with_capacity(1024);
+    b.alloc(0_u8);
+    let used = |b: &Bump| b.allocated_bytes_per_chunk().sum::<usize>();
+    let before = used(&b);
+
+    let x = b
+        .try_collect_slice((0..20_u32).filter(|n| n % 2 == 0))
+        .unwrap();
+    assert_eq!(x.len(), 10);
+    assert!(used(&b) - before <= 10 * mem::size_of::<u32>() + mem::align_of::<u32>());
+}
+
+#[test]
+fn try_collect_slice_with_loose_hints() {
+    let used = |b: &Bump| b.allocated_bytes_per_chunk().sum::<usize>();
+
+    // The upper bound fits in the chunk: it is reserved, and the unused part
+    // is given back.
+    let b = Bump::with_capacity(4096);
+    let x = b
+        .try_collect_slice((0..400_u64).filter(|n| n % 100 == 0))
+        .unwrap();
+    assert_eq!(x, [0, 100, 200, 300]);
+    assert_eq!(used(&b), 4 * mem::size_of::<u64>());
+    assert_eq!(b.chunk_capacities().count(), 1);
+
+    // The upper bound doesn't fit: growing from the lower bound keeps the
+    // slice in the current chunk when it only needs a little space.
+    let b = Bump::with_capacity(1024);
+    let x = b
+        .try_collect_slice((0..1_000_000_u64).filter(|n| n % 100_000 == 0))
+        .unwrap();
+    assert_eq!(x.len(), 10);
+    assert_eq!(used(&b), 10 * mem::size_of::<u64>());
+    assert_eq!(b.chunk_capacities().count(), 1);
+
+    // Growing past the chunk moves the slice to a new, larger one.
+    let b = Bump::with_capacity(64);
+    let x = b
+        .try_collect_slice((0..10_000_u32).filter(|n| n % 2 == 0))
+        .unwrap();
+    assert!(x.iter().copied().eq((0..10_000).step_by(2)));
+}
+
+#[test]
+fn try_collect_slice_survives_allocation_during_iteration() {
+    let b = Bump::new();
+    let x = b
+        .try_collect_slice((0..50_u64).filter(|n| {
+            // Allocating from within the iterator means that the slice is no
+            // longer the most recent allocation when it needs to grow.
+            b.alloc(*n);
+            n % 2 == 1
+        }))
+        .unwrap();
+    assert!(x.iter().copied().eq((1..50).step_by(2)));
+}
+
+#[test]
+fn try_collect_slice_drops_collected_on_failure() {
+    let drops = Cell::new(0);
+    let made = Cell::new(0);
+    let b = Bump::new();
+    b.set_allocation_limit(Some(1024));
+
+    let result = b.try_collect_slice((0..10_000).filter(|n| n % 2 == 0).map(|_| {
+        made.set(made.get() + 1);
+        ([0_u8; 16], CountDrops(&drops))
+    }));
+    assert!(result.is_err());
+    assert!(made.get() > 0);
+    assert_eq!(drops.get(), made.get());
+}
+
+#[test]
+fn try_collect_slice_drops_collected_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _ = b.try_collect_slice((0..10).filter(|_| true).map(|n| {
+            if n == 7 {
+                panic!("eighth element");
+            }
+            CountDrops(&drops)
+        }));
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 7);
+}
+
+#[test]
+fn try_alloc_slice_fill_copy_and_clone_overflow() {
+    let b = Bump::new();
+    assert!(b.try_alloc_slice_fill_copy(usize::MAX, 0_u64).is_err());
+    assert!(b
+        .try_alloc_slice_fill_copy(usize::MAX / 2 + 1, 0_u16)
+        .is_err());
+    assert!(b
+        .try_alloc_slice_fill_clone(usize::MAX / 8, &String::new())
+        .is_err());
+    assert_eq!(b.allocated_bytes(), 0);
+
+    assert_eq!(b.try_alloc_slice_fill_copy(3, 9_u8).unwrap(), &[9, 9, 9]);
+    let s = "hi".to_string();
+    assert_eq!(b.try_alloc_slice_fill_clone(2, &s).unwrap(), ["hi", "hi"]);
+}
+
+#[test]
+fn try_alloc_slice_fill_clone_drops_clones_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    struct PanicsOnThirdClone<'a> {
+        clones: &'a Cell<usize>,
+        drops: &'a Cell<usize>,
+    }
+
+    impl Clone for PanicsOnThirdClone<'_> {
+        fn clone(&self) -> Self {
+            if self.clones.get() == 2 {
+                panic!("third clone");
+            }
+            self.clones.set(self.clones.get() + 1);
+            PanicsOnThirdClone {
+                clones: self.clones,
+                drops: self.drops,
+            }
+        }
+    }
+
+    impl Drop for PanicsOnThirdClone<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    let clones = Cell::new(0);
+    let drops = Cell::new(0);
+    let value = PanicsOnThirdClone {
+        clones: &clones,
+        drops: &drops,
+    };
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _ = b.try_alloc_slice_fill_clone(10, &value);
+    }));
+    assert!(result.is_err());
+    assert_eq!(clones.get(), 2);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn alloc_slice_try_fill_iter_rewinds_on_error() {
+    let drops = Cell::new(0);
+    let b = Bump::with_capacity(1024);
+    b.alloc(0_u8);
+    let capacity = b.chunk_capacity();
+
+    let result: Result<&mut [CountDrops], ()> = b.alloc_slice_try_fill_iter((0..10).map(|i| {
+        if i == 6 {
+            Err(())
+        } else {
+            Ok(CountDrops(&drops))
+        }
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 6);
+    assert_eq!(b.chunk_capacity(), capacity);
+
+    // An allocation made while filling keeps the slice's space reserved.
+    let result: Result<&mut [u64], ()> = b.alloc_slice_try_fill_with(10, |i| {
+        if i == 0 {
+            b.alloc(1_u8);
+        }
+        if i == 5 {
+            Err(())
+        } else {
+            Ok(i as u64)
+        }
+    });
+    assert!(result.is_err());
+    assert!(b.chunk_capacity() < capacity - 10 * mem::size_of::<u64>());
+}
+
+#[test]
+fn alloc_slice_try_fill_with_rewinds_on_error() {
+    let b = Bump::with_capacity(4096);
+    // Misalign the bump pointer so that the slice needs padding too.
+    b.alloc(0_u8);
+    let allocated = b.allocated_bytes();
+    let used: usize = b.allocated_bytes_per_chunk().sum();
+
+    let len = 100;
+    let result: Result<&mut [u64], usize> =
+        b.alloc_slice_try_fill_with(len, |i| if i == len / 2 { Err(i) } else { Ok(i as u64) });
+    assert_eq!(result, Err(len / 2));
+    assert_eq!(b.allocated_bytes(), allocated);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), used);
+
+    // The rewound space is handed out again.
+    let x = b.alloc(1_u8);
+    assert_eq!(*x, 1);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), used + 1);
+}
+
+#[test]
+fn alloc_slice_try_fill_with_drops_initialized_on_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _: Result<_, ()> = b.alloc_slice_try_fill_with(10, |i| {
+            if i == 3 {
+                panic!("fourth element");
+            }
+            Ok(CountDrops(&drops))
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn alloc_slice_fill_copy_large_slices_of_various_sizes() {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Odd([u8; 3]);
+
+    let b = Bump::new();
+    for len in [0, 1, 2, 3, 7, 64, 1000, 100_000] {
+        assert!(b
+            .alloc_slice_fill_copy(len, 0xa5_u8)
+            .iter()
+            .all(|&x| x == 0xa5));
+        assert!(b
+            .alloc_slice_fill_copy(len, 0xbeef_u16)
+            .iter()
+            .all(|&x| x == 0xbeef));
+        assert!(b
+            .alloc_slice_fill_copy(len, u32::MAX - 1)
+            .iter()
+            .all(|&x| x == u32::MAX - 1));
+        assert!(b
+            .alloc_slice_fill_copy(len, 1_u64 << 40)
+            .iter()
+            .all(|&x| x == 1 << 40));
+        let odd = b.alloc_slice_fill_copy(len, Odd([1, 2, 3]));
+        assert_eq!(odd.len(), len);
+        assert!(odd.iter().all(|&x| x == Odd([1, 2, 3])));
+    }
+}
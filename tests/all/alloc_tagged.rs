@@ -0,0 +1,56 @@
+use bumpalo::Bump;
+
+#[test]
+fn iter_tagged_filters_by_tag_and_type() {
+    let b = Bump::new();
+    b.alloc_tagged(1, 10_u64);
+    b.alloc_tagged(2, 20_u64);
+    b.alloc_tagged(1, "not a u64");
+    b.alloc(99_u64);
+    b.alloc_tagged(1, 30_u64);
+
+    let ones: Vec<u64> = b.iter_tagged::<u64>(1).copied().collect();
+    assert_eq!(ones, [30, 10]);
+
+    let twos: Vec<u64> = b.iter_tagged::<u64>(2).copied().collect();
+    assert_eq!(twos, [20]);
+
+    let strs: Vec<&str> = b.iter_tagged::<&str>(1).copied().collect();
+    assert_eq!(strs, ["not a u64"]);
+
+    assert_eq!(b.iter_tagged::<u64>(3).count(), 0);
+}
+
+#[test]
+fn iter_tagged_across_chunks() {
+    let b = Bump::with_capacity(64);
+    for i in 0..1000_u32 {
+        b.alloc_tagged(i % 2, i);
+    }
+    assert!(b.chunk_capacities().count() > 1);
+
+    let evens: Vec<u32> = b.iter_tagged::<u32>(0).copied().collect();
+    assert_eq!(evens, (0..1000).step_by(2).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_tagged_after_reset() {
+    let mut b = Bump::new();
+    b.alloc_tagged(0, [1_u8; 16]);
+    b.reset();
+    assert_eq!(b.iter_tagged::<[u8; 16]>(0).count(), 0);
+
+    b.alloc_tagged(0, [2_u8; 16]);
+    let all: Vec<_> = b.iter_tagged::<[u8; 16]>(0).collect();
+    assert_eq!(all, [&[2_u8; 16]]);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn alloc_tagged_header_overhead() {
+    let b = Bump::new();
+    b.alloc(0_u64);
+    let before = b.chunk_capacity();
+    b.alloc_tagged(0, 0_u64);
+    assert_eq!(before - b.chunk_capacity(), 32 + 8);
+}
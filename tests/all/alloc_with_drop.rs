@@ -0,0 +1,199 @@
+use bumpalo::{Bump, ChunkSource};
+use std::alloc::{alloc, dealloc, Layout};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+struct Logged(u32, Arc<Mutex<Vec<u32>>>);
+
+impl Drop for Logged {
+    fn drop(&mut self) {
+        self.1.lock().unwrap().push(self.0);
+    }
+}
+
+struct PanicOnDrop;
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        panic!("finalizer panicked");
+    }
+}
+
+fn log() -> Arc<Mutex<Vec<u32>>> {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+fn dropped(log: &Arc<Mutex<Vec<u32>>>) -> Vec<u32> {
+    log.lock().unwrap().clone()
+}
+
+#[test]
+fn reset_runs_destructors_in_reverse_order() {
+    let log = log();
+    let mut b = Bump::new();
+    for i in 0..3 {
+        b.alloc_with_drop(Logged(i, log.clone()));
+        // Plain allocations are never dropped.
+        b.alloc(Logged(100 + i, log.clone()));
+    }
+    let x = b.alloc_with_drop(Logged(3, log.clone()));
+    x.0 = 30;
+    assert!(dropped(&log).is_empty());
+
+    b.reset();
+    assert_eq!(dropped(&log), [30, 2, 1, 0]);
+
+    // The drop list is cleared, so nothing is dropped twice.
+    b.reset();
+    assert_eq!(dropped(&log), [30, 2, 1, 0]);
+
+    b.alloc_with_drop(Logged(4, log.clone()));
+    b.reset_keeping_first();
+    assert_eq!(dropped(&log), [30, 2, 1, 0, 4]);
+}
+
+#[test]
+fn dropping_the_arena_runs_destructors() {
+    let log = log();
+    let b = Bump::with_capacity(64);
+    for i in 0..100 {
+        b.alloc_with_drop(Logged(i, log.clone()));
+    }
+    assert!(b.chunk_capacities().count() > 1);
+    drop(b);
+    assert_eq!(dropped(&log), (0..100).rev().collect::<Vec<_>>());
+    assert_eq!(Arc::strong_count(&log), 1);
+}
+
+#[test]
+fn checkpoint_runs_destructors_of_scoped_values() {
+    let log = log();
+    let mut b = Bump::new();
+    b.alloc_with_drop(Logged(0, log.clone()));
+    b.with_checkpoint(|scope| {
+        scope.alloc_with_drop(Logged(1, log.clone()));
+        scope.with_checkpoint(|inner| {
+            inner.alloc_with_drop(Logged(2, log.clone()));
+        });
+        assert_eq!(dropped(&log), [2]);
+        scope.alloc_with_drop(Logged(3, log.clone()));
+    });
+    assert_eq!(dropped(&log), [2, 3, 1]);
+    drop(b);
+    assert_eq!(dropped(&log), [2, 3, 1, 0]);
+}
+
+#[test]
+fn types_without_drop_glue_have_no_header() {
+    let b = Bump::new();
+    b.alloc_with_drop(0_u64);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), 8);
+}
+
+#[test]
+fn failed_allocation_drops_the_value() {
+    let log = log();
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_with_drop(Logged(7, log.clone())).is_err());
+    assert_eq!(dropped(&log), [7]);
+}
+
+#[test]
+fn reset_finishes_when_a_destructor_panics() {
+    let log = log();
+    let mut b = Bump::with_capacity(64);
+    for i in 0..50 {
+        b.alloc_with_drop(Logged(i, log.clone()));
+    }
+    b.alloc_with_drop(PanicOnDrop);
+    b.alloc_with_drop(Logged(50, log.clone()));
+    assert!(b.chunk_capacities().count() > 1);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| b.reset()));
+    assert!(result.is_err());
+
+    // The destructors after the panicking one still ran, and the arena was
+    // reset all the same.
+    assert_eq!(dropped(&log), (0..51).rev().collect::<Vec<_>>());
+    assert_eq!(Arc::strong_count(&log), 1);
+    assert_eq!(b.chunk_capacities().count(), 1);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), 0);
+
+    // Nothing is dropped twice.
+    b.reset();
+    assert_eq!(dropped(&log).len(), 51);
+}
+
+#[test]
+fn reset_keeping_first_runs_destructors_before_freeing_chunks() {
+    let log = log();
+    let mut b = Bump::with_capacity(64);
+    for i in 0..50 {
+        b.alloc_with_drop(Logged(i, log.clone()));
+    }
+    b.alloc_with_drop(PanicOnDrop);
+    assert!(b.chunk_capacities().count() > 1);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| b.reset_keeping_first()));
+    assert!(result.is_err());
+    assert_eq!(dropped(&log), (0..50).rev().collect::<Vec<_>>());
+    assert_eq!(b.chunk_capacities().count(), 1);
+}
+
+#[test]
+fn dropping_the_arena_frees_chunks_when_a_destructor_panics() {
+    struct Counting(AtomicUsize);
+
+    unsafe impl ChunkSource for Counting {
+        fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            NonNull::new(unsafe { alloc(layout) })
+        }
+
+        unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.fetch_sub(1, Ordering::Relaxed);
+            dealloc(ptr.as_ptr(), layout);
+        }
+    }
+
+    static LIVE: Counting = Counting(AtomicUsize::new(0));
+
+    let log = log();
+    let b = Bump::with_chunk_source(&LIVE);
+    for i in 0..50 {
+        b.alloc_with_drop(Logged(i, log.clone()));
+    }
+    b.alloc_with_drop(PanicOnDrop);
+    b.alloc_with_drop(Logged(50, log.clone()));
+    assert!(LIVE.0.load(Ordering::Relaxed) > 1);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(b)));
+    assert!(result.is_err());
+    assert_eq!(dropped(&log), (0..51).rev().collect::<Vec<_>>());
+    assert_eq!(LIVE.0.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn checkpoint_rewinds_when_a_destructor_panics() {
+    let log = log();
+    let mut b = Bump::new();
+    b.alloc_with_drop(Logged(0, log.clone()));
+    let used = b.allocated_bytes_per_chunk().sum::<usize>();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.with_checkpoint(|scope| {
+            scope.alloc_with_drop(Logged(1, log.clone()));
+            scope.alloc_with_drop(PanicOnDrop);
+            scope.alloc_with_drop(Logged(2, log.clone()));
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(dropped(&log), [2, 1]);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), used);
+
+    drop(b);
+    assert_eq!(dropped(&log), [2, 1, 0]);
+}
@@ -89,3 +89,64 @@ fn small_allocation_limit() {
     bump.set_allocation_limit(Some(64));
     assert!(bump.try_alloc([0; 1]).is_ok());
 }
+
+#[test]
+fn try_alloc_with_does_not_run_closure_on_failure() {
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+
+    let res = b.try_alloc_with(|| -> [u64; 1000] { panic!("closure should not be called") });
+    assert!(res.is_err());
+    assert_eq!(b.allocated_bytes(), 0);
+}
+
+#[test]
+fn reset_single_chunk_keeps_allocated_bytes() {
+    let mut bump = Bump::with_capacity(1024);
+    let before = bump.allocated_bytes();
+    bump.alloc(0_u64);
+    bump.reset();
+    assert_eq!(bump.allocated_bytes(), before);
+}
+
+fn custom_oom_handler() -> ! {
+    panic!("custom oom handler")
+}
+
+#[test]
+#[should_panic(expected = "custom oom handler")]
+fn oom_handler_runs_on_infallible_failure() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_oom_handler(Some(custom_oom_handler));
+    bump.alloc(1_u64);
+}
+
+#[test]
+#[should_panic(expected = "out of memory")]
+fn oom_handler_can_be_cleared() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_oom_handler(Some(custom_oom_handler));
+    bump.set_oom_handler(None);
+    bump.alloc_slice_fill_copy(10, 0_u8);
+}
+
+#[test]
+fn oom_handler_does_not_affect_fallible_methods() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_oom_handler(Some(custom_oom_handler));
+    assert!(bump.try_alloc(1_u64).is_err());
+}
+
+#[test]
+#[cfg(feature = "collections")]
+#[should_panic(expected = "custom oom handler")]
+fn oom_handler_runs_when_collection_grows() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_oom_handler(Some(custom_oom_handler));
+    let mut v = bumpalo::collections::Vec::new_in(&bump);
+    v.push(1_u64);
+}
@@ -266,6 +266,121 @@ fn allocator_shrink_layout_change() {
     }
 }
 
+#[test]
+fn btree_map_in_bump() {
+    use std::collections::BTreeMap;
+
+    let b = AllocatorDebug::new(Bump::new());
+    let mut map = BTreeMap::new_in(&b);
+
+    for round in 0..3 {
+        for i in 0..1000_u32 {
+            map.insert(i, i * round);
+        }
+        assert_eq!(map.len(), 1000);
+        assert!(map.iter().all(|(k, v)| *v == k * round));
+        assert_eq!(map.range(10..20).map(|(k, _)| *k).sum::<u32>(), 145);
+
+        for i in (0..1000).step_by(2) {
+            assert_eq!(map.remove(&i), Some(i * round));
+        }
+        assert_eq!(map.len(), 500);
+
+        map.clear();
+        assert!(map.is_empty());
+    }
+
+    assert!(b.allocs.load(Relaxed) > 0);
+    assert_eq!(b.allocs.load(Relaxed), b.deallocs.load(Relaxed));
+}
+
+#[test]
+fn vec_deque_in_bump() {
+    use std::collections::VecDeque;
+
+    let b = AllocatorDebug::new(Bump::new());
+    let mut deque = VecDeque::new_in(&b);
+
+    for round in 0..3 {
+        // Push onto both ends so that the ring buffer wraps around when it
+        // grows.
+        for i in 0..500_u64 {
+            deque.push_back(i + round);
+            deque.push_front(i + round);
+        }
+        assert_eq!(deque.len(), 1000);
+        assert_eq!(deque.front(), Some(&(499 + round)));
+        assert_eq!(deque.back(), Some(&(499 + round)));
+        assert_eq!(
+            deque.iter().sum::<u64>(),
+            2 * (0..500).map(|i| i + round).sum::<u64>()
+        );
+
+        deque.truncate(10);
+        deque.shrink_to_fit();
+        assert_eq!(deque.len(), 10);
+
+        deque.clear();
+        assert!(deque.is_empty());
+    }
+
+    assert_ne!(b.grows.load(Relaxed), 0);
+    assert_ne!(b.shrinks.load(Relaxed), 0);
+    drop(deque);
+    assert_eq!(b.deallocs.load(Relaxed), 1);
+}
+
+#[test]
+fn allocator_grow_zeroed_from_uninit() {
+    let ref bump = Bump::new();
+
+    // Grow a block whose bytes were never initialized.
+    let first_layout = Layout::from_size_align(16, 8).unwrap();
+    let p = bump.allocate(first_layout).unwrap().cast::<u8>();
+    let second_layout = Layout::from_size_align(64, 8).unwrap();
+    let p = unsafe { bump.grow_zeroed(p, first_layout, second_layout) }.unwrap();
+    assert!(unsafe { p.as_ref() }[16..].iter().all(|b| *b == 0));
+}
+
+#[test]
+#[cfg(feature = "boxed")]
+fn box_into_std_and_back() {
+    use std::cell::Cell;
+
+    struct CountDrops<'a>(&'a Cell<usize>);
+    impl Drop for CountDrops<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    let b = bumpalo::boxed::Box::new_in(CountDrops(&drops), &bump);
+    let ptr = &*b as *const CountDrops;
+
+    let std_box: Box<CountDrops, &Bump> = unsafe { bumpalo::boxed::Box::into_std(b, &bump) };
+    assert_eq!(&*std_box as *const CountDrops, ptr);
+    assert_eq!(drops.get(), 0);
+
+    let b: bumpalo::boxed::Box<CountDrops> = std_box.into();
+    assert_eq!(&*b as *const CountDrops, ptr);
+    assert_eq!(drops.get(), 0);
+
+    drop(b);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+#[cfg(feature = "boxed")]
+fn box_from_std_unsized() {
+    let bump = Bump::new();
+    let std_box: Box<[u32], &Bump> = Box::new_in([1, 2, 3], &bump);
+    let b = bumpalo::boxed::Box::from(std_box);
+    assert_eq!(&*b, &[1, 2, 3]);
+}
+
 fn is_pointer_aligned_to(p: NonNull<[u8]>, align: usize) -> bool {
     debug_assert!(align.is_power_of_two());
 
@@ -274,3 +389,60 @@ fn is_pointer_aligned_to(p: NonNull<[u8]>, align: usize) -> bool {
 
     pointer == pointer_aligned
 }
+
+#[test]
+fn allocator_api_allocate_zeroed() {
+    let mut b = Bump::with_capacity(256);
+    b.alloc_slice_fill_copy(128, 0xff_u8);
+    b.reset();
+
+    let layout = Layout::from_size_align(100, 4).unwrap();
+    let block = (&b).allocate_zeroed(layout).unwrap();
+    assert_eq!(block.len(), 100);
+    assert!(unsafe { block.as_ref() }.iter().all(|&x| x == 0));
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert_eq!((&b).allocate_zeroed(layout), Err(AllocError));
+}
+
+#[test]
+fn deallocate_tip_reclaims_space() {
+    let bump = Bump::new();
+    bump.alloc(0_u8);
+
+    // Pushing and popping boxes reuses the same memory every time. (The
+    // alignment padding before the first box is not given back.)
+    let first = {
+        let b = Box::new_in(0_u64, &bump);
+        &*b as *const u64
+    };
+    let allocated = bump.allocated_bytes();
+    let used: usize = bump.allocated_bytes_per_chunk().sum();
+    for i in 0..10_000_u64 {
+        let b = Box::new_in(i, &bump);
+        assert_eq!(&*b as *const u64, first);
+    }
+    assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), used);
+
+    // So does a `Vec` that is created, filled and dropped.
+    for _ in 0..1000 {
+        let mut v = Vec::with_capacity_in(16, &bump);
+        v.extend(0..16_u32);
+    }
+    assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), used);
+    assert_eq!(bump.allocated_bytes(), allocated);
+}
+
+#[test]
+fn deallocate_not_tip_is_noop() {
+    let bump = Bump::new();
+    let a = Box::new_in(1_u64, &bump);
+    let b = Box::new_in(2_u64, &bump);
+    let used: usize = bump.allocated_bytes_per_chunk().sum();
+
+    drop(a);
+    assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), used);
+    drop(b);
+    assert!(bump.allocated_bytes_per_chunk().sum::<usize>() < used);
+}
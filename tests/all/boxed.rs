@@ -12,3 +12,124 @@ fn into_raw_aliasing() {
     let mut_ref = unsafe { &mut *raw };
     dbg!(mut_ref);
 }
+
+#[test]
+fn leak_does_not_run_destructor() {
+    use std::cell::Cell;
+
+    struct Dropper<'a>(&'a Cell<usize>);
+
+    impl Drop for Dropper<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    {
+        let bump = Bump::new();
+        let boxed = Box::new_in(Dropper(&drops), &bump);
+        let leaked: &mut Dropper = Box::leak(boxed);
+        assert_eq!(leaked.0.get(), 0);
+    }
+    assert_eq!(drops.get(), 0);
+
+    {
+        let bump = Bump::new();
+        let _boxed = Box::new_in(Dropper(&drops), &bump);
+    }
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn str_box_as_hash_map_key() {
+    use std::collections::HashMap;
+
+    let bump = Bump::new();
+    let boxed_str = |s: &str| -> Box<str> { unsafe { Box::from_raw(bump.alloc_str(s)) } };
+
+    let a = boxed_str("hello");
+    let b = boxed_str("hello");
+    let c = boxed_str("world");
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a < c);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+    assert_eq!(format!("{} {:?}", a, c), "hello \"world\"");
+
+    let mut map = HashMap::new();
+    map.insert(a, 1);
+    map.insert(c, 2);
+    assert_eq!(map.insert(b, 3), Some(1));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("hello"), Some(&3));
+    assert_eq!(map[&boxed_str("world")], 2);
+}
+
+#[test]
+fn unsize_to_trait_object() {
+    use std::cell::Cell;
+
+    trait Shape {
+        fn area(&self) -> u32;
+    }
+
+    struct Square<'a>(u32, &'a Cell<usize>);
+
+    impl Shape for Square<'_> {
+        fn area(&self) -> u32 {
+            self.0 * self.0
+        }
+    }
+
+    impl Drop for Square<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    struct Rect(u32, u32);
+
+    impl Shape for Rect {
+        fn area(&self) -> u32 {
+            self.0 * self.1
+        }
+    }
+
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    {
+        let square = Box::new_in(Square(3, &drops), &bump);
+        let rect = Box::new_in(Rect(2, 5), &bump);
+        let shapes: [Box<dyn Shape>; 2] = unsafe {
+            [
+                Box::from_raw(Box::into_raw(square) as *mut dyn Shape),
+                Box::from_raw(Box::into_raw(rect) as *mut dyn Shape),
+            ]
+        };
+        assert_eq!(shapes.iter().map(|s| s.area()).sum::<u32>(), 19);
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 1);
+
+    let shape: &mut dyn Shape = bump.alloc(Rect(4, 4));
+    assert_eq!(shape.area(), 16);
+}
+
+#[test]
+fn clone_in() {
+    let bump = Bump::new();
+    let original = Box::new_in(String::from("hello"), &bump);
+
+    let mut same_arena = Box::clone_in(&original, &bump);
+    same_arena.push_str(", world");
+
+    let other_bump = Bump::new();
+    let mut other_arena = Box::clone_in(&original, &other_bump);
+    other_arena.make_ascii_uppercase();
+
+    assert_eq!(*original, "hello");
+    assert_eq!(*same_arena, "hello, world");
+    assert_eq!(*other_arena, "HELLO");
+    assert!(other_bump.allocated_bytes() > 0);
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "canaries")]
+
+use bumpalo::Bump;
+
+fn overrun(buf: &mut [u8]) {
+    unsafe {
+        buf.as_mut_ptr().add(buf.len()).write(0);
+    }
+}
+
+#[test]
+fn in_bounds_writes_pass() {
+    let mut b = Bump::with_canaries();
+    for i in 0..1000_u32 {
+        let buf = b.alloc_slice_fill_copy(i as usize % 17 + 1, 0_u8);
+        buf.fill(0xff);
+        b.alloc(i);
+    }
+    b.check_canaries();
+    b.reset();
+    b.alloc_str("after reset");
+}
+
+#[test]
+fn canaries_count_toward_allocated_bytes() {
+    let b = Bump::with_canaries();
+    b.alloc(0_u8);
+    let before: usize = b.allocated_bytes_per_chunk().sum();
+    b.alloc(0_u8);
+    let after: usize = b.allocated_bytes_per_chunk().sum();
+    assert!(after - before > 8);
+}
+
+#[test]
+fn alloc_layout_excess_has_no_excess() {
+    let b = Bump::<8>::with_min_align();
+    let (_, excess) = b.alloc_layout_excess(std::alloc::Layout::new::<u8>());
+    assert_eq!(excess, 7);
+
+    let b = Bump::with_canaries();
+    let (_, excess) = b.alloc_layout_excess(std::alloc::Layout::new::<u8>());
+    assert_eq!(excess, 0);
+}
+
+#[test]
+#[should_panic(expected = "allocation 1 was overrun")]
+fn check_reports_overrun_allocation() {
+    let b = Bump::with_canaries();
+    b.alloc_slice_fill_copy(3, 0_u8);
+    let buf = b.alloc_slice_fill_copy(3, 0_u8);
+    b.alloc_slice_fill_copy(3, 0_u8);
+    overrun(buf);
+    b.check_canaries();
+}
+
+#[test]
+#[should_panic(expected = "allocation 0 was overrun")]
+fn reset_checks_canaries() {
+    let mut b = Bump::with_canaries();
+    let buf = b.alloc_slice_fill_copy(5, 0_u8);
+    overrun(buf);
+    b.reset();
+}
+
+#[test]
+#[should_panic(expected = "allocation 2 was overrun")]
+fn drop_checks_canaries() {
+    let b = Bump::with_canaries();
+    b.alloc(1_u64);
+    b.alloc(2_u64);
+    let buf = b.alloc_slice_fill_copy(8, 0_u8);
+    overrun(buf);
+}
+
+#[test]
+fn reset_forgets_old_canaries() {
+    let mut b = Bump::with_canaries();
+    b.alloc_slice_fill_copy(100, 0_u8);
+    b.reset();
+    // Reuses the memory where the old canary was.
+    let buf = b.alloc_slice_fill_copy(100, 0xff_u8);
+    buf.fill(0xff);
+    b.check_canaries();
+}
+
+#[test]
+fn checkpoint_forgets_scoped_canaries() {
+    let mut b = Bump::with_canaries();
+    b.alloc(0_u32);
+    b.with_checkpoint(|scope| {
+        scope.alloc_slice_fill_copy(64, 0_u8);
+    });
+    let buf = b.alloc_slice_fill_copy(64, 0_u8);
+    buf.fill(0xff);
+    b.check_canaries();
+}
+
+#[test]
+fn unguarded_arenas_are_not_checked() {
+    let b = Bump::new();
+    let buf = b.alloc_slice_fill_copy(3, 0_u8);
+    buf.fill(1);
+    b.check_canaries();
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn growing_vec_keeps_canaries_intact() {
+    let b = Bump::with_canaries();
+    let mut v = bumpalo::collections::Vec::new_in(&b);
+    for i in 0..1000_u32 {
+        v.push(i);
+    }
+    b.check_canaries();
+}
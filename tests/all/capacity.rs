@@ -5,3 +5,500 @@ fn try_with_capacity_too_large() {
     // Shouldn't panic even though the capacity is too large for a `Layout`.
     let _ = Bump::try_with_capacity(isize::MAX as usize + 1);
 }
+
+#[test]
+fn capacity_for_fits_in_one_chunk() {
+    let mut bump = Bump::with_capacity(Bump::<1>::capacity_for::<u64>(1000));
+    for i in 0..1000_u64 {
+        bump.alloc(i);
+    }
+    assert_eq!(bump.iter_allocated_chunks().count(), 1);
+}
+
+#[test]
+fn capacity_for_accounts_for_alignment() {
+    #[repr(align(64))]
+    struct Aligned(#[allow(dead_code)] u8);
+
+    let mut bump = Bump::with_capacity(Bump::<1>::capacity_for::<Aligned>(100));
+    for _ in 0..100 {
+        bump.alloc(Aligned(0));
+    }
+    assert_eq!(bump.iter_allocated_chunks().count(), 1);
+
+    let mut bump = Bump::<8>::with_min_align_and_capacity(Bump::<8>::capacity_for::<u8>(5000));
+    for i in 0..5000 {
+        bump.alloc(i as u8);
+    }
+    assert_eq!(bump.iter_allocated_chunks().count(), 1);
+}
+
+#[test]
+fn chunk_capacities() {
+    let bump = Bump::new();
+    assert_eq!(bump.chunk_capacities().count(), 0);
+
+    bump.alloc(1_u8);
+    let first: Vec<usize> = bump.chunk_capacities().collect();
+    assert_eq!(first.len(), 1);
+    assert!(first[0] >= bump.chunk_capacity());
+
+    // Fill the current chunk so that a bigger one gets allocated.
+    bump.alloc_slice_fill_copy(first[0], 0_u8);
+    let both: Vec<usize> = bump.chunk_capacities().collect();
+    assert_eq!(both.len(), 2);
+    assert_eq!(both[1], first[0]);
+    assert!(both[0] >= first[0]);
+    assert_eq!(both.iter().sum::<usize>(), bump.allocated_bytes());
+}
+
+#[test]
+fn allocated_bytes_per_chunk() {
+    let bump = Bump::new();
+    assert_eq!(bump.allocated_bytes_per_chunk().count(), 0);
+
+    bump.alloc(1_u8);
+    bump.alloc(2_u8);
+    assert_eq!(bump.allocated_bytes_per_chunk().collect::<Vec<_>>(), [2]);
+
+    // Fill the rest of the current chunk, then spill into a new one.
+    let capacity = bump.chunk_capacities().next().unwrap();
+    bump.alloc_slice_fill_copy(capacity - 2, 0_u8);
+    bump.alloc(3_u32);
+    let used: Vec<usize> = bump.allocated_bytes_per_chunk().collect();
+    assert_eq!(used, [4, capacity]);
+
+    for (used, capacity) in bump
+        .allocated_bytes_per_chunk()
+        .zip(bump.chunk_capacities())
+    {
+        assert!(used <= capacity);
+    }
+}
+
+#[test]
+fn min_chunk_size() {
+    let bump = Bump::new();
+    bump.set_min_chunk_size(1 << 20);
+
+    bump.alloc(0_u8);
+    let first = bump.chunk_capacities().next().unwrap();
+    assert!(first >= 1 << 20);
+
+    // Doubling still applies above the floor.
+    bump.alloc_layout(std::alloc::Layout::from_size_align(first, 1).unwrap());
+    let second = bump.chunk_capacities().next().unwrap();
+    assert!(second >= 2 * first);
+
+    // Lowering the floor doesn't touch existing chunks.
+    bump.set_min_chunk_size(0);
+    assert_eq!(bump.chunk_capacities().count(), 2);
+}
+
+#[test]
+fn chunk_growth_factor() {
+    fn grow(bump: &Bump) -> usize {
+        let first = bump.chunk_capacities().next().unwrap();
+        bump.alloc_layout(std::alloc::Layout::from_size_align(first + 1, 1).unwrap());
+        bump.chunk_capacities().next().unwrap()
+    }
+
+    let doubling = Bump::with_capacity(1 << 20);
+    let first = doubling.chunk_capacity();
+    assert!(grow(&doubling) >= 2 * first);
+
+    let bump = Bump::with_capacity(1 << 20);
+    bump.set_chunk_growth_factor(3, 2);
+    assert_eq!(bump.chunk_growth_factor(), (3, 2));
+    let first = bump.chunk_capacity();
+    let second = grow(&bump);
+    assert!(second >= first * 3 / 2);
+    assert!(second < 2 * first);
+    let third = grow(&bump);
+    assert!(third >= second * 3 / 2);
+    assert!(third < 2 * second);
+}
+
+#[test]
+fn chunk_growth_factor_large_numerator() {
+    // Just above one, with terms whose product doesn't fit in 32 bits.
+    let bump = Bump::with_capacity(1 << 20);
+    bump.set_chunk_growth_factor(u32::MAX, u32::MAX - 1);
+    let first = bump.chunk_capacity();
+    bump.alloc_layout(std::alloc::Layout::from_size_align(first + 1, 1).unwrap());
+    let second = bump.chunk_capacities().next().unwrap();
+    assert!(second > first);
+    assert!(second < first * 3 / 2);
+}
+
+#[test]
+fn chunk_growth_factor_overflowing_usize() {
+    // The grown size overflows `usize` on 32-bit targets and saturates; on
+    // 64-bit ones it is still far too large to allocate. Either way, it is
+    // halved until the allocator accepts it.
+    let bump = Bump::with_capacity(1 << 20);
+    bump.set_chunk_growth_factor(u32::MAX, 1);
+    let first = bump.chunk_capacity();
+    bump.alloc_layout(std::alloc::Layout::from_size_align(first + 1, 1).unwrap());
+    assert_eq!(bump.chunk_capacities().count(), 2);
+    assert!(bump.chunk_capacities().next().unwrap() > first);
+}
+
+#[test]
+fn chunk_growth_factor_still_fits_allocation() {
+    let bump = Bump::with_capacity(1 << 16);
+    bump.set_chunk_growth_factor(5, 4);
+    let big = bump.alloc_slice_fill_copy(1 << 20, 1_u8);
+    assert_eq!(big.len(), 1 << 20);
+    assert!(bump.chunk_capacities().next().unwrap() >= 1 << 20);
+}
+
+#[test]
+#[should_panic(expected = "chunk growth factor must be greater than one")]
+fn chunk_growth_factor_must_grow() {
+    Bump::new().set_chunk_growth_factor(2, 2);
+}
+
+#[test]
+#[should_panic(expected = "chunk growth factor must be greater than one")]
+fn chunk_growth_factor_zero_denominator() {
+    Bump::new().set_chunk_growth_factor(1, 0);
+}
+
+#[test]
+fn min_chunk_size_respects_allocation_limit() {
+    let bump = Bump::new();
+    bump.set_min_chunk_size(1 << 20);
+    bump.set_allocation_limit(Some(1 << 16));
+
+    bump.alloc(0_u8);
+    assert!(bump.allocated_bytes() <= 1 << 16);
+}
+
+#[test]
+fn current_chunk_allocated_bytes() {
+    let bump = Bump::new();
+    assert_eq!(bump.current_chunk_allocated_bytes(), 0);
+
+    for i in 0..1000_u64 {
+        bump.alloc(i);
+        assert_eq!(
+            Some(bump.current_chunk_allocated_bytes()),
+            bump.allocated_bytes_per_chunk().next()
+        );
+    }
+    assert!(bump.chunk_capacities().count() > 1);
+}
+
+#[test]
+fn new_like() {
+    let old = Bump::<8>::with_min_align();
+    for i in 0..10_000_u64 {
+        old.alloc(i);
+    }
+    let total: usize = old.chunk_capacities().sum();
+
+    let mut new = Bump::new_like(&old);
+    assert_eq!(new.min_align(), 8);
+    assert_eq!(new.allocated_bytes(), new.chunk_capacity());
+    assert!(new.chunk_capacity() >= total);
+    for i in 0..10_000_u64 {
+        new.alloc(i);
+    }
+    assert_eq!(new.iter_allocated_chunks().count(), 1);
+
+    let empty = Bump::new();
+    let new = Bump::try_new_like(&empty).unwrap();
+    assert_eq!(new.chunk_capacities().count(), 0);
+}
+
+#[test]
+fn memory_report() {
+    let bump = Bump::new();
+    assert_eq!(
+        bump.memory_report(),
+        "Bump { chunks: 0, used: 0 B, capacity: 0 B, waste: 0 B }"
+    );
+
+    let bump = Bump::with_capacity(100);
+    let capacity = bump.chunk_capacity();
+    assert!(capacity < 1024);
+    bump.alloc([0_u8; 10]);
+    assert_eq!(
+        bump.memory_report(),
+        format!(
+            "Bump {{ chunks: 1, used: 10 B, capacity: {} B, waste: {} B }}",
+            capacity,
+            capacity - 10
+        )
+    );
+
+    let bump = Bump::with_capacity(3 << 20);
+    bump.alloc_layout(std::alloc::Layout::from_size_align(1536, 1).unwrap());
+    let report = bump.memory_report();
+    assert!(
+        report.starts_with("Bump { chunks: 1, used: 1.5 KiB, capacity: 3.0 MiB, waste: "),
+        "{report}"
+    );
+}
+
+#[test]
+fn total_capacity_tracks_chunks() {
+    let mut bump = Bump::new();
+    let sum = |b: &Bump| b.chunk_capacities().sum::<usize>();
+    assert_eq!(bump.total_capacity(), 0);
+
+    for i in 0..1000_u64 {
+        bump.alloc([i; 7]);
+        assert_eq!(bump.total_capacity(), sum(&bump));
+    }
+    assert!(bump.chunk_capacities().count() > 1);
+
+    // Over-aligned allocations get a dedicated chunk behind the current one.
+    bump.alloc_layout(std::alloc::Layout::from_size_align(8, 1 << 14).unwrap());
+    assert_eq!(bump.total_capacity(), sum(&bump));
+
+    bump.reset();
+    assert_eq!(bump.chunk_capacities().count(), 1);
+    assert_eq!(bump.total_capacity(), sum(&bump));
+    assert_eq!(bump.total_capacity(), bump.chunk_capacity());
+}
+
+#[test]
+fn max_satisfiable_align() {
+    use std::alloc::Layout;
+
+    assert_eq!(Bump::new().max_satisfiable_align(), 0);
+    assert_eq!(Bump::<8>::with_min_align().max_satisfiable_align(), 0);
+
+    for offset in [0, 1, 3, 100, 1000] {
+        // The alignment fits in the current chunk...
+        let bump = Bump::with_capacity(4096);
+        bump.alloc_layout(Layout::from_size_align(offset, 1).unwrap());
+        let align = bump.max_satisfiable_align();
+        assert!(align.is_power_of_two());
+        let chunks = bump.chunk_capacities().count();
+        bump.alloc_layout(Layout::from_size_align(1, align).unwrap());
+        assert_eq!(bump.chunk_capacities().count(), chunks, "offset {offset}");
+
+        // ...but twice the alignment needs a new chunk.
+        let bump = Bump::with_capacity(4096);
+        bump.alloc_layout(Layout::from_size_align(offset, 1).unwrap());
+        let align = bump.max_satisfiable_align();
+        let chunks = bump.chunk_capacities().count();
+        bump.alloc_layout(Layout::from_size_align(1, align * 2).unwrap());
+        assert!(bump.chunk_capacities().count() > chunks, "offset {offset}");
+    }
+
+    let bump = Bump::<16>::with_min_align_and_capacity(64);
+    bump.alloc_layout(Layout::from_size_align(bump.chunk_capacity() - 16, 1).unwrap());
+    assert_eq!(bump.max_satisfiable_align(), 16);
+    bump.alloc(0_u8);
+    assert_eq!(bump.max_satisfiable_align(), 0);
+}
+
+#[test]
+fn large_allocation_threshold_keeps_current_chunk() {
+    let bump = Bump::with_capacity(1024);
+    bump.set_large_allocation_threshold(Some(512));
+    bump.alloc([0_u8; 800]);
+    let free = bump.chunk_capacity();
+
+    let big = bump.alloc([7_u8; 4096]);
+    assert!(big.iter().all(|&b| b == 7));
+    assert_eq!(bump.chunk_capacity(), free);
+    assert_eq!(bump.chunk_capacities().count(), 2);
+    assert_eq!(
+        bump.total_capacity(),
+        bump.chunk_capacities().sum::<usize>()
+    );
+
+    // Small allocations carry on filling the first chunk...
+    bump.alloc([0_u8; 100]);
+    assert_eq!(bump.chunk_capacity(), free - 100);
+    assert_eq!(bump.chunk_capacities().count(), 2);
+
+    // ...until they don't fit either, at which point a new chunk is made
+    // current as usual.
+    bump.alloc_slice_fill_copy(free, 0_u8);
+    assert_eq!(bump.chunk_capacities().count(), 3);
+
+    // Allocations below the threshold are unaffected.
+    let bump = Bump::with_capacity(1024);
+    bump.set_large_allocation_threshold(Some(4096));
+    bump.alloc([0_u8; 800]);
+    let free = bump.chunk_capacity();
+    bump.alloc([0_u8; 1024]);
+    assert_ne!(bump.chunk_capacity(), free);
+}
+
+#[test]
+fn large_allocation_threshold_reset() {
+    let mut bump = Bump::with_capacity(1024);
+    bump.set_large_allocation_threshold(Some(512));
+    bump.alloc(1_u8);
+    let capacity = bump.chunk_capacities().next().unwrap();
+    for _ in 0..10 {
+        bump.alloc([0_u8; 2048]);
+    }
+    assert_eq!(bump.chunk_capacities().count(), 11);
+
+    // The first chunk stayed current, so it's the one that is kept.
+    bump.reset();
+    assert_eq!(bump.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+}
+
+#[test]
+fn bytes_since_commit_point() {
+    let b = Bump::with_capacity(256);
+    let used = |b: &Bump| b.allocated_bytes_per_chunk().sum::<usize>();
+
+    let start = b.commit_point();
+    assert_eq!(b.bytes_since(start), 0);
+    b.alloc([0_u8; 10]);
+    assert_eq!(b.bytes_since(start), 10);
+
+    let marker = b.commit_point();
+    let before = used(&b);
+    b.alloc(0_u8);
+    b.alloc(0_u64);
+    assert_eq!(b.bytes_since(marker), used(&b) - before);
+
+    // Across chunk boundaries, the unused tails of older chunks don't count.
+    let marker = b.commit_point();
+    let before = used(&b);
+    for _ in 0..5 {
+        let capacity = b.chunk_capacity();
+        b.alloc_slice_fill_copy(capacity + 1, 0_u8);
+        b.alloc([0_u8; 3]);
+    }
+    assert!(b.chunk_capacities().count() > 5);
+    assert_eq!(b.bytes_since(marker), used(&b) - before);
+    assert_eq!(b.bytes_since(start), used(&b));
+}
+
+#[test]
+fn bytes_since_counts_dedicated_chunks() {
+    #[repr(align(4096))]
+    struct PageAligned(#[allow(dead_code)] [u8; 4096]);
+
+    let used = |b: &Bump| b.allocated_bytes_per_chunk().sum::<usize>();
+    let b = Bump::with_capacity(256);
+    b.alloc(1_u32);
+    let marker = b.commit_point();
+    let before = used(&b);
+    b.alloc(PageAligned([0; 4096]));
+    b.alloc(2_u32);
+    assert!(b.bytes_since(marker) >= 4096 + 4);
+    assert_eq!(b.bytes_since(marker), used(&b) - before);
+
+    b.set_large_allocation_threshold(Some(1024));
+    let marker = b.commit_point();
+    b.alloc_slice_fill_copy(10_000, 0_u8);
+    assert_eq!(b.bytes_since(marker), 10_000);
+
+    // An empty arena counts everything.
+    let b = Bump::new();
+    let marker = b.commit_point();
+    b.alloc_slice_fill_copy(100, 0_u8);
+    assert_eq!(b.bytes_since(marker), 100);
+}
+
+#[test]
+fn alloc_layout_in_chunk_never_allocates_a_chunk() {
+    use std::alloc::Layout;
+
+    let bump = Bump::new();
+    // No chunk yet, so nothing fits.
+    assert!(bump.alloc_layout_in_chunk(Layout::new::<u8>()).is_none());
+    assert_eq!(bump.chunk_capacities().count(), 0);
+
+    let bump = Bump::with_capacity(1000);
+    for size in [1, 7, 100, 333, 1000, 4096, 1, 1] {
+        let capacity = bump.chunk_capacity();
+        let layout = Layout::from_size_align(size, 1).unwrap();
+        let result = bump.alloc_layout_in_chunk(layout);
+        assert_eq!(result.is_some(), size <= capacity, "size {size}");
+        if result.is_some() {
+            assert_eq!(bump.chunk_capacity(), capacity - size);
+        } else {
+            assert_eq!(bump.chunk_capacity(), capacity);
+        }
+        assert_eq!(bump.chunk_capacities().count(), 1);
+    }
+
+    // Aligned allocations get aligned pointers from the same chunk.
+    let bump = Bump::with_capacity(64);
+    bump.alloc(1_u8);
+    let capacity = bump.chunk_capacity();
+    let layout = Layout::from_size_align(capacity + 1, 8).unwrap();
+    assert!(bump.alloc_layout_in_chunk(layout).is_none());
+    let p = bump.alloc_layout_in_chunk(Layout::new::<u64>()).unwrap();
+    assert_eq!(p.as_ptr() as usize % 8, 0);
+    assert_eq!(bump.chunk_capacities().count(), 1);
+
+    // Zero-sized layouts always succeed.
+    assert!(bump
+        .alloc_layout_in_chunk(Layout::from_size_align(0, 64).unwrap())
+        .is_some());
+}
+
+#[test]
+fn reset_max_retained_drops_oversized_chunk() {
+    let mut bump = Bump::with_capacity(4096);
+    bump.alloc_slice_fill_copy(1 << 20, 1_u8);
+    bump.reset();
+    assert!(bump.total_capacity() >= 1 << 20);
+
+    bump.alloc_slice_fill_copy(1 << 20, 1_u8);
+    bump.reset_max_retained(8192);
+    assert_eq!(bump.chunk_capacities().count(), 1);
+    assert!(bump.total_capacity() >= 8192);
+    assert!(bump.total_capacity() < 1 << 20);
+    assert_eq!(bump.chunk_capacity(), bump.total_capacity());
+
+    // The replacement chunk is kept by later calls, rather than being
+    // replaced again every time.
+    let p = bump.alloc(1_u8) as *const u8;
+    bump.reset_max_retained(8192);
+    assert_eq!(bump.alloc(1_u8) as *const u8, p);
+
+    // And it is also the first chunk now.
+    bump.alloc_slice_fill_copy(1 << 16, 1_u8);
+    bump.reset_keeping_first();
+    assert!(bump.total_capacity() < 1 << 16);
+}
+
+#[test]
+fn reset_max_retained_keeps_small_chunk() {
+    let mut bump = Bump::with_capacity(1024);
+    let capacity = bump.chunk_capacity();
+    let p = bump.alloc(1_u64) as *const u64;
+
+    bump.reset_max_retained(1 << 20);
+    assert_eq!(bump.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+    assert_eq!(bump.alloc(2_u64) as *const u64, p);
+}
+
+#[test]
+fn reset_max_retained_zero_keeps_nothing() {
+    let mut bump = Bump::with_capacity(1024);
+    bump.alloc(1_u8);
+    bump.reset_max_retained(0);
+    assert_eq!(bump.total_capacity(), 0);
+    assert_eq!(bump.chunk_capacities().count(), 0);
+
+    assert_eq!(*bump.alloc(3_u8), 3);
+    assert_eq!(bump.chunk_capacities().count(), 1);
+}
+
+#[test]
+fn reset_max_retained_respects_allocation_limit() {
+    let mut bump = Bump::new();
+    bump.alloc_slice_fill_copy(1 << 16, 1_u8);
+    bump.set_allocation_limit(Some(1024));
+    bump.reset_max_retained(4096);
+    // A 4096-byte chunk would be over the limit, so none is kept.
+    assert_eq!(bump.total_capacity(), 0);
+    assert!(bump.try_alloc(1_u8).is_ok());
+}
@@ -0,0 +1,137 @@
+use bumpalo::Bump;
+use std::alloc::Layout;
+use std::panic::{self, AssertUnwindSafe};
+
+fn used(b: &Bump) -> usize {
+    b.allocated_bytes_per_chunk().sum()
+}
+
+#[test]
+fn with_checkpoint_rewinds_and_returns_value() {
+    let mut b = Bump::with_capacity(1024);
+    b.alloc(1_u32);
+    let before = used(&b);
+
+    let s = b.with_checkpoint(|scope| {
+        let s = scope.alloc_str("scoped");
+        assert!(used(scope) > before);
+        s.len()
+    });
+    assert_eq!(s, 6);
+    assert_eq!(used(&b), before);
+}
+
+#[test]
+fn with_checkpoint_frees_new_chunks() {
+    let mut b = Bump::with_capacity(64);
+    b.alloc(1_u8);
+    let capacities: Vec<usize> = b.chunk_capacities().collect();
+    let total = b.total_capacity();
+
+    b.with_checkpoint(|scope| {
+        for i in 0..10_000_u64 {
+            scope.alloc(i);
+        }
+        // Over-aligned allocations get their own chunks.
+        scope.alloc_layout(Layout::from_size_align(8, 1 << 14).unwrap());
+        assert!(scope.chunk_capacities().count() > 2);
+    });
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), capacities);
+    assert_eq!(b.total_capacity(), total);
+
+    // Dedicated chunks linked in behind the checkpointed chunk are freed too.
+    b.with_checkpoint(|scope| {
+        scope.alloc_layout(Layout::from_size_align(8, 1 << 14).unwrap());
+        assert_eq!(scope.chunk_capacities().count(), 2);
+    });
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), capacities);
+    assert_eq!(b.total_capacity(), total);
+}
+
+#[test]
+fn with_checkpoint_on_empty_arena() {
+    let mut b = Bump::new();
+    b.with_checkpoint(|scope| {
+        scope.alloc([0_u8; 100]);
+    });
+    assert_eq!(b.chunk_capacities().count(), 0);
+    assert_eq!(b.allocated_bytes(), 0);
+
+    // The arena still works, and `reset_keeping_first` keeps the chunk
+    // allocated after the checkpoint.
+    b.alloc(1_u8);
+    b.reset_keeping_first();
+    assert_eq!(b.chunk_capacities().count(), 1);
+}
+
+#[test]
+fn nested_checkpoints_rewind_in_lifo_order() {
+    let mut b = Bump::with_capacity(1024);
+    let start = used(&b);
+
+    b.with_checkpoint(|outer| {
+        let x = outer.alloc(1_u64);
+        *x += 1;
+        let after_outer = used(outer);
+
+        let inner_result = outer.with_checkpoint(|inner| {
+            inner.alloc(2_u64);
+            let after_inner = used(inner);
+            inner.with_checkpoint(|innermost| {
+                innermost.alloc_slice_fill_copy(5000, 0_u8);
+            });
+            assert_eq!(used(inner), after_inner);
+            after_inner
+        });
+        assert!(inner_result > after_outer);
+        assert_eq!(used(outer), after_outer);
+
+        let y = outer.alloc(3_u64);
+        assert_eq!(*y, 3);
+    });
+    assert_eq!(used(&b), start);
+}
+
+#[test]
+fn with_checkpoint_rewinds_on_panic() {
+    let mut b = Bump::with_capacity(64);
+    b.alloc(1_u8);
+    let capacities: Vec<usize> = b.chunk_capacities().collect();
+    let before = used(&b);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.with_checkpoint(|scope| {
+            scope.alloc_slice_fill_copy(10_000, 0_u8);
+            panic!("in scope");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), capacities);
+    assert_eq!(used(&b), before);
+}
+
+#[test]
+fn with_checkpoint_restores_tagged_allocations() {
+    let mut b = Bump::new();
+    b.alloc_tagged(0, 1_u32);
+    b.with_checkpoint(|scope| {
+        scope.alloc_tagged(0, 2_u32);
+        assert_eq!(scope.iter_tagged::<u32>(0).count(), 2);
+    });
+    assert_eq!(b.iter_tagged::<u32>(0).copied().collect::<Vec<_>>(), [1]);
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn with_checkpoint_collections() {
+    use bumpalo::collections::Vec;
+
+    let mut b = Bump::new();
+    let len = b.with_checkpoint(|scope| {
+        let mut v = Vec::new_in(scope);
+        v.extend(0..1000_u32);
+        v.len()
+    });
+    assert_eq!(len, 1000);
+    assert_eq!(b.allocated_bytes(), 0);
+}
@@ -0,0 +1,154 @@
+use bumpalo::{Bump, ChunkSource};
+use std::alloc::{alloc, Layout};
+use std::ptr::NonNull;
+use std::sync::Mutex;
+
+/// A chunk source that keeps freed chunks on a free list and hands them out
+/// again for requests with the same layout.
+#[derive(Default)]
+struct Pool {
+    // Addresses rather than pointers, so that the pool is `Sync`.
+    free: Mutex<Vec<(usize, Layout)>>,
+    live: Mutex<usize>,
+    fresh: Mutex<usize>,
+}
+
+impl Pool {
+    fn leaked() -> &'static Pool {
+        Box::leak(Box::new(Pool::default()))
+    }
+
+    fn live(&self) -> usize {
+        *self.live.lock().unwrap()
+    }
+
+    fn fresh(&self) -> usize {
+        *self.fresh.lock().unwrap()
+    }
+
+    fn pooled(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+}
+
+unsafe impl ChunkSource for Pool {
+    fn alloc_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        *self.live.lock().unwrap() += 1;
+        let mut free = self.free.lock().unwrap();
+        if let Some(i) = free.iter().position(|&(_, l)| l == layout) {
+            let (addr, _) = free.swap_remove(i);
+            return NonNull::new(addr as *mut u8);
+        }
+        *self.fresh.lock().unwrap() += 1;
+        NonNull::new(unsafe { alloc(layout) })
+    }
+
+    unsafe fn free_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        *self.live.lock().unwrap() -= 1;
+        self.free
+            .lock()
+            .unwrap()
+            .push((ptr.as_ptr() as usize, layout));
+    }
+}
+
+#[test]
+fn chunks_come_from_and_go_back_to_the_source() {
+    let pool = Pool::leaked();
+    let mut bump = Bump::with_chunk_source(pool);
+    assert_eq!(pool.live(), 0);
+
+    bump.alloc(1_u8);
+    assert_eq!(pool.live(), 1);
+
+    // Force a few more chunks.
+    for _ in 0..3 {
+        bump.alloc_slice_fill_copy(bump.chunk_capacity() + 1, 0_u8);
+    }
+    let chunks = bump.iter_allocated_chunks().count();
+    assert_eq!(pool.live(), chunks);
+
+    // `reset` gives back every chunk but the current one.
+    bump.reset();
+    assert_eq!(pool.live(), 1);
+    assert_eq!(pool.pooled(), chunks - 1);
+
+    drop(bump);
+    assert_eq!(pool.live(), 0);
+    assert_eq!(pool.pooled(), chunks);
+}
+
+#[test]
+fn chunks_are_recycled_across_arenas() {
+    let pool = Pool::leaked();
+
+    let bump = Bump::with_chunk_source(pool);
+    assert_eq!(*bump.alloc(1_u64), 1);
+    drop(bump);
+    assert_eq!(pool.fresh(), 1);
+
+    let bump = Bump::with_chunk_source(pool);
+    assert_eq!(*bump.alloc(2_u64), 2);
+    assert_eq!(pool.fresh(), 1, "the first arena's chunk was reused");
+    assert_eq!(pool.live(), 1);
+}
+
+#[test]
+fn failing_source_fails_allocation() {
+    struct Empty;
+
+    unsafe impl ChunkSource for Empty {
+        fn alloc_chunk(&self, _: Layout) -> Option<NonNull<u8>> {
+            None
+        }
+
+        unsafe fn free_chunk(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!("no chunk was ever handed out")
+        }
+    }
+
+    let bump = Bump::with_chunk_source(Empty);
+    assert!(bump.try_alloc(1_u32).is_err());
+}
+
+#[test]
+fn min_align_with_chunk_source() {
+    let pool = Pool::leaked();
+    let bump = Bump::<16>::with_min_align_and_chunk_source(pool);
+    let x = bump.alloc(1_u8);
+    assert_eq!(x as *mut u8 as usize % 16, 0);
+    drop(bump);
+    assert_eq!(pool.live(), 0);
+}
+
+#[test]
+fn over_aligned_allocations_use_the_source() {
+    let pool = Pool::leaked();
+    let bump = Bump::with_chunk_source(pool);
+    bump.alloc(1_u8);
+
+    let layout = Layout::from_size_align(8, 1 << 16).unwrap();
+    let p = bump.alloc_layout(layout);
+    assert_eq!(p.as_ptr() as usize % (1 << 16), 0);
+    assert_eq!(pool.live(), 2);
+
+    drop(bump);
+    assert_eq!(pool.live(), 0);
+}
+
+#[test]
+fn checkpoint_gives_chunks_back_to_the_source() {
+    let pool = Pool::leaked();
+    let mut bump = Bump::with_chunk_source(pool);
+    bump.alloc(1_u8);
+    assert_eq!(pool.live(), 1);
+
+    bump.with_checkpoint(|scope| {
+        for _ in 0..3 {
+            scope.alloc_slice_fill_copy(scope.chunk_capacity() + 1, 0_u8);
+        }
+        assert_eq!(pool.live(), 4);
+    });
+    assert_eq!(pool.live(), 1);
+    assert_eq!(pool.pooled(), 3);
+}
@@ -1,17 +1,27 @@
-#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api, btreemap_alloc))]
 
+mod alloc_cyclic;
 mod alloc_fill;
+mod alloc_tagged;
 mod alloc_try_with;
 mod alloc_with;
+mod alloc_with_drop;
 mod allocation_limit;
 mod allocator_api;
 mod boxed;
+mod canaries;
 mod capacity;
+mod checkpoint;
+mod chunk_source;
 mod collect_in;
+mod nested;
 mod quickcheck;
 mod quickchecks;
+mod rc;
+mod sharded;
 mod string;
 mod tests;
+mod tracking;
 mod try_alloc_try_with;
 mod try_alloc_with;
 mod vec;
@@ -0,0 +1,81 @@
+use bumpalo::{Bump, NestedBump};
+
+fn used_bytes(b: &Bump) -> usize {
+    b.allocated_bytes_per_chunk().sum()
+}
+
+#[test]
+fn chunks_are_allocated_in_the_parent() {
+    let parent = Bump::new();
+    let child = Bump::new_in(&parent);
+    assert_eq!(parent.allocated_bytes(), 0);
+
+    let x = child.alloc(42_u64);
+    assert_eq!(*x, 42);
+    assert_eq!(parent.chunk_index_of(x as *const u64), Some(0));
+    assert!(used_bytes(&parent) >= child.chunk_capacities().sum::<usize>());
+}
+
+#[test]
+fn dropping_child_gives_tip_chunk_back() {
+    let parent = Bump::new();
+    parent.alloc(1_u64);
+    let used = used_bytes(&parent);
+
+    let child = Bump::new_in(&parent);
+    child.alloc_slice_fill_copy(100, 0_u8);
+    assert!(used_bytes(&parent) > used);
+
+    drop(child);
+    assert_eq!(used_bytes(&parent), used);
+}
+
+#[test]
+fn child_reset_and_growth() {
+    let parent = Bump::new();
+    let mut child = Bump::new_in(&parent);
+
+    // Make the child allocate several chunks, all of them inside the parent.
+    for i in 0..10_000_u64 {
+        let x = child.alloc(i);
+        assert!(parent.chunk_index_of(x as *const u64).is_some());
+    }
+    assert!(child.chunk_capacities().count() > 1);
+
+    child.reset();
+    assert_eq!(child.chunk_capacities().count(), 1);
+    assert_eq!(*child.alloc(7_u32), 7);
+}
+
+#[test]
+fn arena_tree() {
+    let mut root = Bump::new();
+    for _ in 0..3 {
+        let child = Bump::new_in(&root);
+        let grandchild = Bump::new_in(&child);
+        let s = grandchild.alloc_str("leaf");
+        assert!(child.chunk_index_of(s.as_ptr()).is_some());
+        assert!(root.chunk_index_of(s.as_ptr()).is_some());
+    }
+    root.reset();
+    assert_eq!(root.chunk_capacities().count(), 1);
+}
+
+#[test]
+fn with_min_align_in() {
+    let parent = Bump::new();
+    let child: NestedBump<'_, 16> = Bump::with_min_align_in(&parent);
+    let x = child.alloc(1_u8);
+    assert_eq!(x as *const u8 as usize % 16, 0);
+}
+
+#[test]
+fn nested_in_checkpoint_scope() {
+    let mut parent = Bump::new();
+    let used = used_bytes(&parent);
+    parent.with_checkpoint(|scope| {
+        let child = Bump::new_in(scope);
+        assert_eq!(*child.alloc(3_u16), 3);
+    });
+    assert_eq!(used_bytes(&parent), used);
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "rc")]
+
+use bumpalo::rc::Rc;
+use bumpalo::Bump;
+use std::cell::Cell;
+
+struct CountDrops<'a>(u32, &'a Cell<u32>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+#[test]
+fn drops_value_when_last_strong_reference_is_dropped() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    let a = Rc::new_in(CountDrops(1, &drops), &bump);
+    let b = a.clone();
+    let c = Rc::clone(&b);
+    assert_eq!(Rc::strong_count(&a), 3);
+    assert_eq!(c.0, 1);
+
+    drop(a);
+    drop(c);
+    assert_eq!(drops.get(), 0);
+    assert_eq!(Rc::strong_count(&b), 1);
+
+    drop(b);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn weak_upgrade() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    let strong = Rc::new_in(CountDrops(7, &drops), &bump);
+    let weak = Rc::downgrade(&strong);
+    let weak2 = weak.clone();
+    assert!(weak.ptr_eq(&weak2));
+    assert_eq!(weak.strong_count(), 1);
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded.0, 7);
+    assert_eq!(Rc::strong_count(&strong), 2);
+    assert!(Rc::ptr_eq(&strong, &upgraded));
+
+    drop(upgraded);
+    drop(strong);
+    assert_eq!(drops.get(), 1);
+    assert_eq!(weak.strong_count(), 0);
+    assert!(weak.upgrade().is_none());
+    assert!(weak2.upgrade().is_none());
+}
+
+#[test]
+fn try_unwrap() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    let a = Rc::new_in(CountDrops(3, &drops), &bump);
+    let weak = Rc::downgrade(&a);
+    let b = a.clone();
+
+    let a = match Rc::try_unwrap(a) {
+        Ok(_) => panic!("should not unwrap while another strong reference exists"),
+        Err(a) => a,
+    };
+    drop(b);
+
+    let value = Rc::try_unwrap(a).ok().unwrap();
+    assert_eq!(value.0, 3);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(drops.get(), 0);
+
+    drop(value);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn shared_dag_nodes() {
+    struct Node<'a> {
+        value: u32,
+        children: Vec<Rc<'a, Node<'a>>>,
+    }
+
+    fn sum(node: &Node) -> u32 {
+        node.value + node.children.iter().map(|c| sum(c)).sum::<u32>()
+    }
+
+    let bump = Bump::new();
+    let leaf = Rc::new_in(
+        Node {
+            value: 1,
+            children: vec![],
+        },
+        &bump,
+    );
+    let left = Rc::new_in(
+        Node {
+            value: 10,
+            children: vec![leaf.clone()],
+        },
+        &bump,
+    );
+    let right = Rc::new_in(
+        Node {
+            value: 100,
+            children: vec![leaf.clone()],
+        },
+        &bump,
+    );
+    let root = Rc::new_in(
+        Node {
+            value: 1000,
+            children: vec![left, right],
+        },
+        &bump,
+    );
+
+    assert_eq!(sum(&root), 1112);
+    assert_eq!(Rc::strong_count(&leaf), 3);
+    drop(root);
+    assert_eq!(Rc::strong_count(&leaf), 1);
+}
+
+#[test]
+fn forwards_traits() {
+    use std::collections::HashSet;
+
+    let bump = Bump::new();
+    let a = Rc::new_in(String::from("a"), &bump);
+    let b = Rc::new_in(String::from("b"), &bump);
+    assert!(a < b);
+    assert_ne!(a, b);
+    assert_eq!(a, Rc::new_in(String::from("a"), &bump));
+    assert_eq!(format!("{} {:?}", a, b), "a \"b\"");
+
+    let set: HashSet<_> = [a.clone(), b, a].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "std")]
+
+use bumpalo::ShardedBump;
+use std::thread;
+
+#[test]
+fn sharded_alloc_from_many_threads() {
+    let mut arena = ShardedBump::new(8);
+    let values: Vec<Vec<&u64>> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8_u64)
+            .map(|t| {
+                let arena = &arena;
+                s.spawn(move || (0..1000).map(|i| &*arena.alloc(t * 1000 + i)).collect())
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for (t, vals) in values.iter().enumerate() {
+        assert!(vals
+            .iter()
+            .map(|v| **v)
+            .eq((0..1000).map(|i| t as u64 * 1000 + i)));
+    }
+    assert!(arena.allocated_bytes() >= 8 * 1000 * 8);
+}
+
+#[test]
+fn sharded_iter_allocated_chunks_covers_all_shards() {
+    let mut arena = ShardedBump::new(3);
+    thread::scope(|s| {
+        for _ in 0..3 {
+            s.spawn(|| {
+                arena.alloc_str("hello");
+                arena.alloc_slice_copy(&[1_u8, 2, 3]);
+            });
+        }
+    });
+
+    let used: usize = arena.iter_allocated_chunks().map(|c| c.len()).sum();
+    assert_eq!(used, 3 * 8);
+
+    arena.reset();
+    assert_eq!(
+        arena
+            .iter_allocated_chunks()
+            .map(|c| c.len())
+            .sum::<usize>(),
+        0
+    );
+}
+
+#[test]
+fn sharded_thread_keeps_its_shard() {
+    let arena = ShardedBump::new(4);
+    for i in 0..100_u32 {
+        arena.alloc(i);
+    }
+    let shards = arena.into_shards();
+    assert_eq!(shards.iter().filter(|b| b.allocated_bytes() > 0).count(), 1);
+}
+
+#[test]
+fn sharded_more_threads_than_shards() {
+    let mut arena = ShardedBump::new(1);
+    arena.alloc(1_u32);
+    thread::scope(|s| {
+        s.spawn(|| assert!(arena.try_alloc(2_u32).is_err()));
+    });
+
+    // Resetting releases the claim, so another thread can take the shard.
+    arena.reset();
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(*arena.alloc(3_u32), 3));
+    });
+    assert!(arena.try_alloc(4_u32).is_err());
+}
+
+#[test]
+#[should_panic(expected = "claimed by other threads")]
+fn sharded_alloc_panics_without_a_shard() {
+    let arena = ShardedBump::new(1);
+    thread::scope(|s| {
+        s.spawn(|| arena.alloc(1_u32));
+    });
+    arena.alloc(2_u32);
+}
+
+#[test]
+fn sharded_into_shards() {
+    let arena = ShardedBump::new(2);
+    arena.alloc(1_u32);
+    let shards = arena.into_shards();
+    assert_eq!(shards.len(), 2);
+    assert!(shards.iter().map(|b| b.allocated_bytes()).sum::<usize>() > 0);
+}
+
+#[test]
+#[should_panic(expected = "at least one shard")]
+fn sharded_zero_shards() {
+    ShardedBump::new(0);
+}
@@ -34,3 +34,107 @@ fn push_str() {
     assert_eq!(s.len(), 4009);
     assert_eq!(&s[s.len() - 5..], "xxghi");
 }
+
+#[test]
+fn write_and_writeln_multiple_args() {
+    let b = Bump::new();
+    let mut s = String::new_in(&b);
+    let name = "a";
+    write!(s, "{}-{:02}-{:>3}", name, 7, 'z').unwrap();
+    writeln!(s, " {:?} {x}", (1, 2), x = 3.5).unwrap();
+    writeln!(s).unwrap();
+    s.write_char('!').unwrap();
+    assert_eq!(s, "a-07-  z (1, 2) 3.5\n\n!");
+}
+
+#[test]
+fn reserve_then_push_str_does_not_reallocate() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("hello", &b);
+
+    s.reserve(1000);
+    assert!(s.capacity() >= 1005);
+    let ptr = s.as_ptr();
+    let allocated = b.allocated_bytes();
+
+    while s.len() + 5 <= 1005 {
+        s.push_str("world");
+    }
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(b.allocated_bytes(), allocated);
+
+    let mut s = String::from_str_in("hello", &b);
+    s.reserve_exact(10);
+    assert_eq!(s.capacity(), 15);
+    let ptr = s.as_ptr();
+    s.push_str("0123456789");
+    assert_eq!(s.as_ptr(), ptr);
+}
+
+#[test]
+fn std_string_round_trip() {
+    use bumpalo::collections::ToBumpString;
+
+    let b = Bump::new();
+    let std_string = std::string::String::from("héllo wörld");
+
+    let s = String::from_string_in(std_string.clone(), &b);
+    assert_eq!(s, std_string.as_str());
+    assert_eq!(s.len(), std_string.len());
+
+    let t = std_string.to_bump_string_in(&b);
+    let back: &str = &t;
+    assert_eq!(back, std_string);
+    assert_eq!(std::string::String::from(back), std_string);
+
+    assert!("".to_bump_string_in(&b).is_empty());
+}
+
+#[test]
+fn extend_with_chars_and_strs() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("abc", &b);
+
+    s.extend("déf".chars());
+    assert_eq!(s, "abcdéf");
+
+    s.extend(['!', '?'].iter());
+    assert_eq!(s, "abcdéf!?");
+
+    let words: &[&str] = &[" one", " two", ""];
+    s.extend(words.iter().copied());
+    assert_eq!(s, "abcdéf!? one two");
+
+    s.extend(vec![String::from_str_in(" three", &b), String::new_in(&b)]);
+    s.extend(vec![std::string::String::from(" four")]);
+    s.extend(vec![std::borrow::Cow::Borrowed(" five")]);
+    assert_eq!(s, "abcdéf!? one two three four five");
+
+    let mut from_empty = String::new_in(&b);
+    from_empty.extend(std::iter::repeat('x').take(100));
+    assert_eq!(from_empty.len(), 100);
+    assert!(from_empty.capacity() >= 100);
+    assert!(from_empty.chars().all(|c| c == 'x'));
+}
+
+#[test]
+fn from_iter_in_chars_and_strs() {
+    let b = Bump::new();
+
+    let alphabet = String::from_iter_in('a'..='z', &b);
+    assert_eq!(alphabet, "abcdefghijklmnopqrstuvwxyz");
+    assert!(alphabet.capacity() >= 26);
+
+    let multibyte = String::from_iter_in("añ€𝄞".chars().rev(), &b);
+    assert_eq!(multibyte, "𝄞€ña");
+    assert_eq!(multibyte.len(), 10);
+
+    let filtered = String::from_iter_in(('a'..='z').filter(|c| "aeiou".contains(*c)), &b);
+    assert_eq!(filtered, "aeiou");
+
+    let words = String::from_str_iter_in(["one", "", "two", "three"], &b);
+    assert_eq!(words, "onetwothree");
+
+    assert!(String::from_iter_in(std::iter::empty(), &b).is_empty());
+    assert!(String::from_str_iter_in(std::iter::empty(), &b).is_empty());
+}
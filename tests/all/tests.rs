@@ -2,6 +2,7 @@ use bumpalo::Bump;
 use std::alloc::Layout;
 use std::fmt::Debug;
 use std::mem;
+use std::ptr::NonNull;
 use std::usize;
 
 #[test]
@@ -108,6 +109,41 @@ fn alloc_with_strong_alignment() {
     b.alloc_layout(Layout::from_size_align(4096, 64).unwrap());
 }
 
+#[test]
+fn alloc_layout_excess() {
+    let b = Bump::new();
+    let first = b.alloc(0_u8) as *mut u8;
+
+    let (p, excess) = b.alloc_layout_excess(Layout::from_size_align(5, 4).unwrap());
+    assert_eq!(excess, 3);
+    // The excess bytes stop short of the previous allocation.
+    assert!(p.as_ptr() as usize + 5 + excess <= first as usize);
+
+    let (_, excess) = b.alloc_layout_excess(Layout::from_size_align(8, 8).unwrap());
+    assert_eq!(excess, 0);
+
+    let (_, excess) = b.alloc_layout_excess(Layout::from_size_align(0, 8).unwrap());
+    assert_eq!(excess, 0);
+
+    let b = Bump::<8>::with_min_align();
+    let (p, excess) = b.alloc_layout_excess(Layout::new::<u8>());
+    assert_eq!(excess, 7);
+    let q = b.alloc(0_u8) as *mut u8;
+    assert_eq!(q as usize + 8, p.as_ptr() as usize);
+}
+
+#[test]
+fn alloc_shared() {
+    let b = Bump::new();
+
+    let x: &u64 = b.alloc_shared(1);
+    let y: &u64 = b.alloc_shared(2);
+    let aliases = [x, x, y];
+
+    assert_eq!(aliases.iter().map(|r| **r).sum::<u64>(), 4);
+    assert!(!core::ptr::eq(x, y));
+}
+
 #[test]
 fn alloc_slice_copy() {
     let b = Bump::new();
@@ -118,6 +154,37 @@ fn alloc_slice_copy() {
     assert_eq!(src, dst);
 }
 
+#[test]
+fn alloc_slice_from_bytes() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        id: u32,
+        value: f32,
+    }
+
+    let records = [Record { id: 1, value: 0.5 }, Record { id: 2, value: -1.0 }];
+    let mut bytes = vec![0_u8];
+    for r in &records {
+        bytes.extend_from_slice(&r.id.to_ne_bytes());
+        bytes.extend_from_slice(&r.value.to_ne_bytes());
+    }
+
+    let b = Bump::new();
+
+    // Deliberately misaligned source bytes.
+    let parsed: &mut [Record] = unsafe { b.alloc_slice_from_bytes(&bytes[1..]).unwrap() };
+    assert_eq!(parsed, records);
+    assert_eq!(parsed.as_ptr() as usize % mem::align_of::<Record>(), 0);
+
+    assert!(unsafe { b.alloc_slice_from_bytes::<Record>(&bytes) }.is_none());
+    assert!(unsafe { b.alloc_slice_from_bytes::<()>(&bytes) }.is_none());
+    assert_eq!(
+        unsafe { b.alloc_slice_from_bytes::<u64>(&[]) }.unwrap(),
+        &[] as &[u64]
+    );
+}
+
 #[test]
 fn alloc_slice_clone() {
     let b = Bump::new();
@@ -128,6 +195,31 @@ fn alloc_slice_clone() {
     assert_eq!(src, dst);
 }
 
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "overlaps its destination allocation")]
+fn alloc_slice_copy_from_reset_memory_panics() {
+    let mut b = Bump::new();
+    let src = b.alloc_slice_copy(&[1_u32, 2, 3, 4]).as_ptr();
+    b.reset();
+
+    // Misuse: the source is arena memory that the next allocation reuses.
+    let src = unsafe { std::slice::from_raw_parts(src, 4) };
+    b.alloc_slice_copy(src);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "overlaps its destination allocation")]
+fn alloc_slice_clone_from_reset_memory_panics() {
+    let mut b = Bump::new();
+    let src = b.alloc_slice_copy(&[1_u32, 2, 3, 4]).as_ptr();
+    b.reset();
+
+    let src = unsafe { std::slice::from_raw_parts(src, 4) };
+    b.alloc_slice_clone(src);
+}
+
 #[test]
 fn small_size_and_large_align() {
     let b = Bump::new();
@@ -135,6 +227,47 @@ fn small_size_and_large_align() {
     b.alloc_layout(layout);
 }
 
+#[test]
+fn huge_align() {
+    const ALIGN: usize = 0x10000;
+
+    for capacity in [0, 100, 0x1000, 0x100000] {
+        let b = Bump::with_capacity(capacity);
+        b.alloc(0_u8);
+
+        for size in [1, 100, ALIGN, ALIGN + 1] {
+            let layout = Layout::from_size_align(size, ALIGN).unwrap();
+            let p = b.alloc_layout(layout);
+            assert_eq!(p.as_ptr() as usize % ALIGN, 0);
+            unsafe { std::ptr::write_bytes(p.as_ptr(), 0xab, size) };
+        }
+    }
+}
+
+#[test]
+fn huge_align_does_not_exhaust_current_chunk() {
+    let mut b = Bump::with_capacity(100);
+    let first = b.alloc(1_u64) as *mut u64 as usize;
+    let capacity = b.chunk_capacity();
+
+    let layout = Layout::from_size_align(1, 0x10000).unwrap();
+    let p = b.alloc_layout(layout);
+    assert_eq!(p.as_ptr() as usize % 0x10000, 0);
+
+    // Subsequent allocations keep going in the original chunk.
+    assert_eq!(b.chunk_capacity(), capacity);
+    let second = b.alloc(2_u64) as *mut u64 as usize;
+    assert_eq!(second, first - 8);
+
+    let chunks: Vec<_> = b.iter_allocated_chunks().map(|c| c.len()).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0], 16);
+    assert!(b.allocated_bytes() >= 0x10000);
+
+    b.reset();
+    assert_eq!(b.iter_allocated_chunks().count(), 1);
+}
+
 fn with_capacity_helper<I, T>(iter: I)
 where
     T: Copy + Debug + Eq,
@@ -197,6 +330,62 @@ fn test_reset() {
     assert_eq!(b.iter_allocated_chunks().count(), 1);
 }
 
+#[test]
+fn test_reset_keeping_first() {
+    let mut b = Bump::with_capacity(128);
+    let capacity = b.chunk_capacity();
+    let start = b.alloc(0u64) as *const u64 as usize;
+
+    for round in 0..3 {
+        for i in 0u64..10_000 {
+            b.alloc(i);
+        }
+        assert!(b.chunk_capacities().count() > 1);
+
+        b.reset_keeping_first();
+        assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+        assert_eq!(b.allocated_bytes(), capacity, "round {round}");
+        assert_eq!(b.alloc(0u64) as *const u64 as usize, start);
+    }
+
+    // An arena without an initial capacity keeps the chunk it allocated
+    // first, not the largest one.
+    let mut b = Bump::new();
+    b.alloc(0u8);
+    let capacity = b.chunk_capacity() + 1;
+    for i in 0u64..10_000 {
+        b.alloc(i);
+    }
+    b.reset_keeping_first();
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+
+    // Nothing to keep.
+    let mut b = Bump::new();
+    b.reset_keeping_first();
+    assert_eq!(b.chunk_capacities().count(), 0);
+}
+
+#[test]
+fn test_reset_keeping_first_with_dedicated_chunk_behind() {
+    let mut b = Bump::with_capacity(100);
+    let capacity = b.chunk_capacity();
+
+    // This is linked in behind the first chunk.
+    b.alloc_layout(Layout::from_size_align(1, 0x10000).unwrap());
+    assert_eq!(b.chunk_capacities().count(), 2);
+
+    b.reset_keeping_first();
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+
+    // Same again, but with more chunks in front of the first one.
+    b.alloc_layout(Layout::from_size_align(1, 0x10000).unwrap());
+    for i in 0u64..10_000 {
+        b.alloc(i);
+    }
+    b.reset_keeping_first();
+    assert_eq!(b.chunk_capacities().collect::<Vec<_>>(), [capacity]);
+}
+
 #[test]
 fn test_alignment() {
     for &alignment in &[2, 4, 8, 16, 32, 64] {
@@ -210,6 +399,20 @@ fn test_alignment() {
     }
 }
 
+#[test]
+fn test_min_align_default_capacity() {
+    let b = Bump::<16>::with_min_align();
+    assert_eq!(b.allocated_bytes(), 0);
+    for x in 0..100_u8 {
+        let x = b.alloc(x);
+        assert_eq!(x as *mut u8 as usize % 16, 0);
+    }
+
+    let b = Bump::<16>::default();
+    let x = b.alloc(0_u8);
+    assert_eq!(x as *mut u8 as usize % 16, 0);
+}
+
 #[test]
 fn test_chunk_capacity() {
     let b = Bump::with_capacity(512);
@@ -218,13 +421,632 @@ fn test_chunk_capacity() {
     assert!(b.chunk_capacity() < orig_capacity);
 }
 
+#[test]
+fn test_debug() {
+    let b = Bump::new();
+    assert_eq!(
+        format!("{b:?}"),
+        "Bump { chunk_count: 0, allocated_bytes: 0, chunk_capacity: 0, \
+         allocation_limit: None }"
+    );
+
+    b.alloc(1_u64);
+    b.set_allocation_limit(Some(1 << 20));
+    let expected = format!(
+        "Bump {{ chunk_count: 1, allocated_bytes: {}, chunk_capacity: {}, \
+         allocation_limit: Some(1048576) }}",
+        b.allocated_bytes(),
+        b.chunk_capacity(),
+    );
+    assert_eq!(format!("{b:?}"), expected);
+}
+
 #[test]
 #[cfg(feature = "allocator_api")]
 fn miri_stacked_borrows_issue_247() {
     let bump = bumpalo::Bump::new();
 
-    let a = Box::into_raw(Box::new_in(1u8, &bump));
+    let (a, _) = Box::into_raw_with_allocator(Box::new_in(1u8, &bump));
     drop(unsafe { Box::from_raw_in(a, &bump) });
 
     let _b = Box::new_in(2u16, &bump);
-}
\ No newline at end of file
+}
+
+#[test]
+fn alloc_with_trailing_capacity() {
+    let b = Bump::new();
+    let before = b.alloc(0_u8) as *mut u8;
+
+    let (header, trailing) = b.alloc_with_trailing_capacity(7_u64, 13);
+    assert_eq!(*header, 7);
+    // 8 + 13 = 21 bytes, rounded up to the `u64` alignment.
+    assert_eq!(trailing.len(), 16);
+
+    let header_addr = header as *mut u64 as usize;
+    assert_eq!(trailing.as_ptr() as usize, header_addr + 8);
+    assert!(header_addr + 8 + trailing.len() <= before as usize);
+
+    for (i, byte) in trailing.iter_mut().enumerate() {
+        byte.write(i as u8);
+    }
+    *header += 1;
+    assert_eq!(*header, 8);
+
+    let (unit, trailing) = b.alloc_with_trailing_capacity((), 3);
+    assert_eq!(*unit, ());
+    assert_eq!(trailing.len(), 3);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hex_dump_multiple_lines_and_chunks() {
+    let mut b = Bump::new();
+    unsafe {
+        assert_eq!(b.hex_dump(), "");
+    }
+
+    b.alloc_slice_copy(&(0..20_u8).collect::<Vec<_>>());
+    let expected = "chunk 0 (20 bytes)\n\
+                    00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|\n\
+                    00000010  10 11 12 13                                       |....|\n";
+    unsafe {
+        assert_eq!(b.hex_dump(), expected);
+    }
+
+    // Force a second chunk; the newest chunk is dumped first.
+    let capacity = b.chunk_capacity();
+    b.alloc_slice_fill_copy(capacity + 1, b'A');
+    let dump = unsafe { b.hex_dump() };
+    assert!(dump.starts_with(&format!("chunk 0 ({} bytes)\n", capacity + 1)));
+    assert!(dump.contains("|AAAAAAAAAAAAAAAA|\n"));
+    assert!(dump.ends_with(expected.replace("chunk 0", "chunk 1").as_str()));
+}
+
+#[test]
+fn alloc_str_bytes() {
+    let b = Bump::new();
+    let bytes = b.alloc_str_bytes("héllo");
+    assert_eq!(bytes, "héllo".as_bytes());
+    bytes[0] = b'j';
+    assert_eq!(bytes, "jéllo".as_bytes());
+    assert!(b.alloc_str_bytes("").is_empty());
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_str_bytes("too big").is_err());
+}
+
+#[test]
+fn iter_allocated_regions_excludes_unused_tail() {
+    let b = Bump::with_capacity(64);
+    b.alloc_slice_copy(b"first");
+    let first_capacity = b.chunk_capacity();
+    b.alloc_slice_fill_copy(first_capacity + 1, b'x');
+    b.alloc_slice_copy(b"second");
+
+    let regions: Vec<&[u8]> = unsafe { b.iter_allocated_regions().collect() };
+    assert_eq!(regions.len(), 2);
+    assert!(regions[0].starts_with(b"second"));
+    assert!(regions[0][6..].iter().all(|&x| x == b'x'));
+    assert_eq!(regions[1], b"first");
+
+    let used: Vec<usize> = b.allocated_bytes_per_chunk().collect();
+    let lens: Vec<usize> = regions.iter().map(|r| r.len()).collect();
+    assert_eq!(lens, used);
+}
+
+#[test]
+fn alloc_slice_clone_calls_clone_per_element() {
+    use std::cell::Cell;
+
+    struct CountClones<'a>(&'a Cell<usize>, u32);
+
+    impl Clone for CountClones<'_> {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            CountClones(self.0, self.1 + 1)
+        }
+    }
+
+    let clones = Cell::new(0);
+    let src: Vec<_> = (0..5).map(|i| CountClones(&clones, i)).collect();
+    let b = Bump::new();
+    let dst = b.alloc_slice_clone(&src);
+    assert_eq!(clones.get(), 5);
+    assert!(dst.iter().map(|c| c.1).eq(1..6));
+
+    let copies = b.alloc_slice_clone(&[1_u32, 2, 3]);
+    assert_eq!(copies, &[1, 2, 3]);
+}
+
+#[test]
+fn alloc_raw_doubly_linked() {
+    use std::ptr::NonNull;
+
+    struct Node {
+        value: u32,
+        prev: Option<NonNull<Node>>,
+        next: Option<NonNull<Node>>,
+    }
+
+    let b = Bump::new();
+    let mut head: Option<NonNull<Node>> = None;
+    for value in (0..10).rev() {
+        let node = b.alloc_raw(Node {
+            value,
+            prev: None,
+            next: head,
+        });
+        if let Some(old) = head {
+            unsafe { (*old.as_ptr()).prev = Some(node) };
+        }
+        head = Some(node);
+    }
+
+    let mut values = vec![];
+    let mut tail = None;
+    let mut cursor = head;
+    while let Some(node) = cursor {
+        unsafe {
+            values.push((*node.as_ptr()).value);
+            tail = Some(node);
+            cursor = (*node.as_ptr()).next;
+        }
+    }
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+
+    let mut backwards = vec![];
+    let mut cursor = tail;
+    while let Some(node) = cursor {
+        unsafe {
+            backwards.push((*node.as_ptr()).value);
+            cursor = (*node.as_ptr()).prev;
+        }
+    }
+    assert_eq!(backwards, (0..10).rev().collect::<Vec<_>>());
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_raw(1_u64).is_err());
+}
+
+#[test]
+fn alloc_slice_from_each_source() {
+    use std::cell::Cell;
+
+    let b = Bump::new();
+    let array = [1_u32, 2, 3];
+    assert_eq!(b.alloc_slice(&array[1..]), &[2, 3]);
+    let names = [String::from("a"), String::from("b")];
+    assert_eq!(b.alloc_slice(&names), ["a", "b"]);
+    assert_eq!(names, ["a", "b"]);
+    assert_eq!(b.alloc_slice(&mut [4_u32, 5][..]), &[4, 5]);
+    assert_eq!(b.alloc_slice([6_u32, 7]), &[6, 7]);
+    assert_eq!(b.alloc_slice(vec![8_u32]), &[8]);
+    assert!(b.alloc_slice(Vec::<String>::new()).is_empty());
+
+    // Owned sources are moved without being cloned.
+    struct NoClone<'a>(&'a Cell<usize>);
+    impl Drop for NoClone<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+    let drops = Cell::new(0);
+    let moved = b.alloc_slice(vec![NoClone(&drops), NoClone(&drops)]);
+    assert_eq!(moved.len(), 2);
+    assert_eq!(drops.get(), 0);
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_slice(&array[..]).is_err());
+}
+
+#[test]
+fn alloc_checked_detects_reset() {
+    let mut b = Bump::new();
+    let g0 = b.generation();
+    let handle = b.alloc_checked(String::from("hello"));
+    let copy = handle;
+    assert_eq!(handle.generation(), g0);
+    assert_eq!(handle.get(&b).map(|s| s.as_str()), Some("hello"));
+    assert_eq!(copy.get(&b).map(|s| s.as_str()), Some("hello"));
+
+    // Handles from one arena are never valid in another.
+    let other = Bump::new();
+    assert_ne!(other.generation(), b.generation());
+    assert!(handle.get(&other).is_none());
+
+    b.reset();
+    assert_ne!(b.generation(), g0);
+    assert!(handle.get(&b).is_none());
+
+    let fresh = b.alloc_checked(1_u8);
+    assert_eq!(fresh.get(&b), Some(&1));
+    b.with_checkpoint(|scope| {
+        let scoped = scope.alloc_checked(2_u8);
+        assert_eq!(scoped.get(scope), Some(&2));
+        assert_eq!(fresh.get(scope), Some(&1));
+    });
+    // Ending a checkpoint scope conservatively invalidates every handle.
+    assert!(fresh.get(&b).is_none());
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_checked(0_u64).is_err());
+}
+
+#[test]
+fn alloc_slice_copy_with_extra_is_contiguous() {
+    let b = Bump::new();
+    let (prefix, suffix) = b.alloc_slice_copy_with_extra(&[1_u32, 2, 3], 2);
+    assert_eq!(prefix, &[1, 2, 3]);
+    assert_eq!(suffix.len(), 2);
+    assert_eq!(prefix.as_ptr_range().end, suffix.as_ptr().cast::<u32>());
+    suffix[0].write(4);
+    suffix[1].write(5);
+    let suffix = unsafe { &*(suffix as *const _ as *const [u32]) };
+    assert_eq!(suffix, &[4, 5]);
+
+    let (prefix, suffix) = b.alloc_slice_copy_with_extra::<u8>(&[], 0);
+    assert!(prefix.is_empty() && suffix.is_empty());
+    let (prefix, suffix) = b.alloc_slice_copy_with_extra(&[7_u8], 0);
+    assert_eq!((&*prefix, suffix.len()), (&[7][..], 0));
+
+    assert!(b
+        .try_alloc_slice_copy_with_extra(&[1_u8], usize::MAX)
+        .is_err());
+    assert!(b
+        .try_alloc_slice_copy_with_extra(&[1_u64], usize::MAX / 16)
+        .is_err());
+}
+
+#[test]
+fn iter_allocated_chunks_shared_through_refcell() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let bump = Rc::new(RefCell::new(Bump::with_capacity(64)));
+    let arena = bump.borrow();
+    let a: &[u8] = arena.alloc_slice_copy(b"abc");
+    let len = arena.chunk_capacity() + 1;
+    let b: &[u8] = arena.alloc_slice_fill_copy(len, b'z');
+
+    let shared: Vec<Vec<u8>> = unsafe { arena.iter_allocated_chunks_shared() }
+        .map(|chunk| chunk.iter().map(|b| unsafe { b.assume_init() }).collect())
+        .collect();
+    assert_eq!(a, b"abc");
+    assert_eq!(b.len(), len);
+    drop(arena);
+
+    let mut owned = bump.borrow_mut();
+    let exclusive: Vec<Vec<u8>> = owned
+        .iter_allocated_chunks()
+        .map(|chunk| chunk.iter().map(|b| unsafe { b.assume_init() }).collect())
+        .collect();
+    assert_eq!(shared, exclusive);
+    assert_eq!(shared.len(), 2);
+    assert!(shared.iter().any(|c| c.as_slice() == b"abc"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn alloc_str_interned_dedupes_until_freed() {
+    let mut b = Bump::with_interner();
+    let a = b.alloc_str_interned("foo") as *const str;
+    assert!(std::ptr::eq(a, b.alloc_str_interned("foo")));
+    assert!(!std::ptr::eq(a, b.alloc_str_interned("bar")));
+    assert!(std::ptr::eq(
+        b.alloc_str_interned(""),
+        b.alloc_str_interned("")
+    ));
+
+    b.with_checkpoint(|scope| {
+        assert!(std::ptr::eq(a, scope.alloc_str_interned("foo")));
+        let scoped = scope.alloc_str_interned("scoped");
+        assert!(std::ptr::eq(scoped, scope.alloc_str_interned("scoped")));
+    });
+    // "scoped" was freed along with the scope, so it is copied again.
+    assert_eq!(b.alloc_str_interned("scoped"), "scoped");
+    assert!(std::ptr::eq(a, b.alloc_str_interned("foo")));
+
+    b.reset();
+    let used: usize = b.allocated_bytes_per_chunk().sum();
+    assert_eq!(b.alloc_str_interned("foo"), "foo");
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), used + 3);
+
+    // Without an interner, every call copies.
+    let plain = Bump::new();
+    let x = plain.alloc_str_interned("foo");
+    assert!(!std::ptr::eq(x, plain.alloc_str_interned("foo")));
+
+    let limited = Bump::with_interner();
+    limited.set_allocation_limit(Some(0));
+    assert!(limited.try_alloc_str_interned("too big").is_err());
+}
+
+#[test]
+fn allocated_ranges_cover_allocations() {
+    let b = Bump::with_capacity(64);
+    assert_eq!(b.allocated_ranges().count(), 1);
+    assert!(b.allocated_ranges().all(|r| r.is_empty()));
+    assert_eq!(Bump::new().allocated_ranges().count(), 0);
+
+    let small = b.alloc(1_u32) as *mut u32 as usize;
+    let big = b.alloc_slice_fill_copy(b.chunk_capacity() + 1, 0_u8);
+    let big_start = big.as_ptr() as usize;
+    let big_end = big_start + big.len();
+
+    let ranges: Vec<_> = b.allocated_ranges().collect();
+    assert_eq!(ranges.len(), 2);
+    let lens: Vec<usize> = ranges.iter().map(|r| r.len()).collect();
+    assert_eq!(lens, b.allocated_bytes_per_chunk().collect::<Vec<_>>());
+
+    let containing = |addr: usize| ranges.iter().position(|r| r.contains(&addr));
+    let small_chunk = containing(small).unwrap();
+    assert_eq!(containing(small + 3), Some(small_chunk));
+    let big_chunk = containing(big_start).unwrap();
+    assert_ne!(small_chunk, big_chunk);
+    assert_eq!(containing(big_end - 1), Some(big_chunk));
+    assert!(ranges[0].end <= ranges[1].start || ranges[1].end <= ranges[0].start);
+}
+
+#[test]
+fn alloc_closures_as_trait_objects() {
+    use std::cell::Cell;
+
+    let b = Bump::new();
+    let calls = Cell::new(0);
+
+    let f: &dyn Fn() -> usize = b.alloc(|| {
+        calls.set(calls.get() + 1);
+        calls.get()
+    });
+    assert_eq!(f(), 1);
+    assert_eq!(f(), 2);
+
+    let mut sum = 0_u64;
+    let mut offset = 10;
+    {
+        let g: &mut dyn FnMut(u64, u64) = b.alloc(|x, y| sum += x * y + offset);
+        g(2, 3);
+        g(4, 5);
+    }
+    offset += 1;
+    assert_eq!((sum, offset), (46, 11));
+
+    // Callbacks of different closure types stored side by side, and a
+    // closure that owns captured state.
+    let log = std::cell::RefCell::new(Vec::new());
+    let mut callbacks: Vec<&mut dyn FnMut(&str)> = Vec::new();
+    let prefix = String::from("a:");
+    let log_ref = &log;
+    callbacks.push(b.alloc(move |s: &str| log_ref.borrow_mut().push(format!("{prefix}{s}"))));
+    let mut count = 0;
+    callbacks.push(b.alloc(|_: &str| count += 1));
+    for (i, callback) in callbacks.iter_mut().enumerate() {
+        callback(&i.to_string());
+        callback("x");
+    }
+    drop(callbacks);
+    assert_eq!(count, 2);
+    assert_eq!(*log.borrow(), ["a:0", "a:x"]);
+
+    // Zero-sized closures don't use any arena space.
+    let before: usize = b.allocated_bytes_per_chunk().sum();
+    let h: &dyn Fn(i32) -> i32 = b.alloc(|x| x + 1);
+    assert_eq!(h(1), 2);
+    assert_eq!(b.allocated_bytes_per_chunk().sum::<usize>(), before);
+}
+
+#[test]
+fn try_alloc_layout_zeroed_zeroes_reused_memory() {
+    let mut b = Bump::with_capacity(256);
+    b.alloc_slice_fill_copy(64, 0xff_u8);
+    b.reset();
+
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let ptr = b.try_alloc_layout_zeroed(layout).unwrap();
+    let bytes = unsafe { std::slice::from_raw_parts(ptr.as_ptr(), 64) };
+    assert!(bytes.iter().all(|&x| x == 0));
+    assert_eq!(ptr.as_ptr() as usize % 8, 0);
+
+    let ptr = b.alloc_layout_zeroed(Layout::new::<[u32; 3]>());
+    assert_eq!(unsafe { ptr.cast::<[u32; 3]>().as_ref() }, &[0; 3]);
+
+    let zst = b.try_alloc_layout_zeroed(Layout::new::<()>()).unwrap();
+    assert_eq!(zst, NonNull::dangling());
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_layout_zeroed(Layout::new::<u64>()).is_err());
+}
+
+#[test]
+fn chunk_index_of_numbers_chunks_from_oldest() {
+    let b = Bump::with_capacity(64);
+    assert_eq!(b.chunk_index_of(&0_u8), None);
+
+    let mut ptrs = Vec::new();
+    for i in 0..5 {
+        let capacity = b.chunk_capacity();
+        let s = b.alloc_slice_fill_copy(capacity + 1, i as u8);
+        ptrs.push((s.as_ptr(), s.len()));
+    }
+    assert_eq!(b.chunk_capacities().count(), 6);
+
+    for (i, &(p, len)) in ptrs.iter().enumerate() {
+        assert_eq!(b.chunk_index_of(p), Some(i + 1));
+        let last = unsafe { p.add(len - 1) };
+        assert_eq!(b.chunk_index_of(last), Some(i + 1));
+    }
+
+    // Unused space and zero-sized allocations aren't in any chunk's
+    // allocated part.
+    let unused = unsafe { ptrs[4].0.sub(1) };
+    assert_eq!(b.chunk_index_of(unused), None);
+    assert_eq!(b.chunk_index_of(b.alloc(())), None);
+}
+
+#[test]
+fn chunk_index_of_shifts_after_dedicated_chunk() {
+    let b = Bump::with_capacity(64);
+    b.alloc_slice_fill_copy(b.chunk_capacity() + 1, 0_u8);
+    let x = b.alloc(1_u32);
+    assert_eq!(b.chunk_index_of(x), Some(1));
+
+    // A large allocation gets its own chunk, which is linked in behind the
+    // current chunk, so the current chunk's index moves up.
+    b.set_large_allocation_threshold(Some(4096));
+    let big = b.alloc_slice_fill_copy(4096, 0_u8);
+    assert_eq!(b.chunk_capacities().count(), 3);
+    assert_eq!(b.chunk_index_of(big.as_ptr()), Some(1));
+    assert_eq!(b.chunk_index_of(x), Some(2));
+}
+
+#[test]
+fn alloc_slice_copy_aligned_respects_alignment() {
+    let b = Bump::new();
+    for align in [1, 2, 8, 64, 4096] {
+        b.alloc(0_u8);
+        let x = b.alloc_slice_copy_aligned(&[1_u16, 2, 3], align);
+        assert_eq!(x, &[1, 2, 3]);
+        assert_eq!(x.as_ptr() as usize % align.max(2), 0);
+    }
+
+    // Never less aligned than `T`.
+    b.alloc(0_u8);
+    let x = b.alloc_slice_copy_aligned(&[1_u64], 1);
+    assert_eq!(x.as_ptr() as usize % mem::align_of::<u64>(), 0);
+
+    let empty = b.alloc_slice_copy_aligned::<u32>(&[], 256);
+    assert!(empty.is_empty());
+    assert_eq!(empty.as_ptr() as usize % 256, 0);
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_slice_copy_aligned(&[1_u8], 16).is_err());
+}
+
+#[test]
+#[should_panic(expected = "alignment must be a power of two")]
+fn alloc_slice_copy_aligned_rejects_bad_alignment() {
+    Bump::new().alloc_slice_copy_aligned(&[1_u8], 3);
+}
+
+#[test]
+fn reset_reuses_addresses_for_identical_allocation_sequences() {
+    fn allocate(b: &Bump) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        for round in 0..10_usize {
+            let s = b.alloc_slice_fill_with(100 + round, |i| (i * round) as u32);
+            ranges.push((s.as_ptr() as usize, s.len()));
+            let x = b.alloc(round as u8);
+            ranges.push((x as *mut u8 as usize, 1));
+            let y = b.alloc_slice_fill_copy(round, 0_u64);
+            ranges.push((y.as_ptr() as usize, y.len()));
+        }
+        ranges
+    }
+
+    // Start with several chunks. Each reset keeps only the newest (and
+    // largest) one, so the arena soon settles on a single chunk, and from
+    // then on the addresses repeat.
+    let mut b = Bump::with_capacity(64);
+    allocate(&b);
+    assert!(b.chunk_capacities().count() > 1);
+    let mut first = Vec::new();
+    for _ in 0..10 {
+        b.reset();
+        first = allocate(&b);
+        if b.chunk_capacities().count() == 1 {
+            break;
+        }
+    }
+    assert_eq!(b.chunk_capacities().count(), 1);
+    for _ in 0..3 {
+        b.reset();
+        assert_eq!(allocate(&b), first);
+    }
+}
+
+#[test]
+fn alloc_unsized_str_and_slices() {
+    let b = Bump::new();
+
+    let s: &mut str = b.alloc_unsized("hello");
+    assert_eq!(s, "hello");
+    let bytes: &mut [u8] = b.alloc_unsized(&[1_u8, 2, 3][..]);
+    assert_eq!(bytes, [1, 2, 3]);
+    let words: &mut [u32] = b.alloc_unsized(&[7_u32; 4][..]);
+    assert_eq!(words, [7; 4]);
+    assert_eq!(words.as_ptr() as usize % mem::align_of::<u32>(), 0);
+
+    let empty: &mut str = b.alloc_unsized("");
+    assert!(empty.is_empty());
+
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+    assert!(b.try_alloc_unsized("too big").is_err());
+    assert!(b.try_alloc_unsized(&[0_u64; 3][..]).is_err());
+}
+
+#[test]
+fn alloc_shared_slices_can_be_aliased() {
+    struct Table<'a> {
+        bytes: &'a [u8],
+    }
+
+    let b = Bump::new();
+    let bytes = b.alloc_slice_copy_shared(&[1_u8, 2, 3]);
+    let first = Table { bytes };
+    let second = Table { bytes };
+    assert_eq!(first.bytes.as_ptr(), second.bytes.as_ptr());
+    assert_eq!(first.bytes, [1, 2, 3]);
+    assert_eq!(second.bytes, [1, 2, 3]);
+
+    let strings = b.alloc_slice_clone_shared(&[String::from("x")]);
+    let aliases = [strings, strings];
+    assert!(aliases.iter().all(|s| s[0] == "x"));
+
+    let s = b.alloc_str_shared("shared");
+    let t = s;
+    assert_eq!(s.as_ptr(), t.as_ptr());
+    assert_eq!(t, "shared");
+}
+
+#[test]
+fn alloc_slice_copy_unchecked_copies_pod() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Pod {
+        a: u8,
+        b: u64,
+    }
+
+    let b = Bump::new();
+    let src: Vec<Pod> = (0..100)
+        .map(|i| Pod {
+            a: i as u8,
+            b: i * 3,
+        })
+        .collect();
+    let copy = unsafe { b.alloc_slice_copy_unchecked(&src) };
+    assert_eq!(copy, &src[..]);
+    assert_ne!(copy.as_ptr(), src.as_ptr());
+    assert_eq!(copy.as_ptr() as usize % mem::align_of::<Pod>(), 0);
+
+    let empty: &mut [Pod] = unsafe { b.alloc_slice_copy_unchecked(&[]) };
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "alloc_slice_copy_unchecked called with a type that needs dropping")]
+fn alloc_slice_copy_unchecked_rejects_drop_glue_in_debug() {
+    let b = Bump::new();
+    let src = [String::from("owned")];
+    // Safety: this panics before copying anything.
+    unsafe {
+        b.alloc_slice_copy_unchecked(&src);
+    }
+}
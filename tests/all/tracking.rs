@@ -0,0 +1,98 @@
+#![cfg(feature = "tracking")]
+
+use bumpalo::Bump;
+use std::alloc::Layout;
+
+#[test]
+fn alignment_histogram_counts_bytes_per_alignment() {
+    #[repr(align(64))]
+    #[allow(dead_code)]
+    struct CacheLine([u8; 64]);
+
+    let bump = Bump::new();
+    assert!(bump.alignment_histogram().is_empty());
+
+    bump.alloc(1_u8);
+    bump.alloc_str("hello");
+    bump.alloc(2_u16);
+    bump.alloc_slice_copy(&[0_u64; 10]);
+    bump.alloc(CacheLine([0; 64]));
+    bump.alloc(CacheLine([0; 64]));
+    bump.alloc_layout(Layout::from_size_align(3, 16).unwrap());
+
+    let histogram: Vec<(usize, usize)> = bump.alignment_histogram().into_iter().collect();
+    assert_eq!(histogram, [(1, 6), (2, 2), (8, 80), (16, 3), (64, 128)]);
+}
+
+#[test]
+fn alignment_histogram_skips_zero_sized_and_failed_allocations() {
+    let bump = Bump::new();
+    bump.alloc(());
+    bump.alloc_slice_copy::<u32>(&[]);
+    assert!(bump.alignment_histogram().is_empty());
+
+    bump.set_allocation_limit(Some(0));
+    assert!(bump.try_alloc(1_u64).is_err());
+    assert!(bump.alignment_histogram().is_empty());
+}
+
+#[test]
+fn alignment_histogram_reset() {
+    let mut bump = Bump::new();
+    bump.alloc(1_u32);
+    assert_eq!(bump.alignment_histogram().get(&4), Some(&4));
+
+    bump.reset();
+    assert!(bump.alignment_histogram().is_empty());
+
+    bump.alloc(1_u32);
+    assert_eq!(bump.alignment_histogram().get(&4), Some(&4));
+}
+
+#[test]
+fn alignment_histogram_rewound_by_checkpoint() {
+    let mut bump = Bump::new();
+    bump.alloc(1_u32);
+
+    bump.with_checkpoint(|scope| {
+        scope.alloc(1_u32);
+        scope.alloc(1_u64);
+        assert_eq!(scope.alignment_histogram().get(&4), Some(&8));
+        assert_eq!(scope.alignment_histogram().get(&8), Some(&8));
+    });
+
+    let histogram: Vec<(usize, usize)> = bump.alignment_histogram().into_iter().collect();
+    assert_eq!(histogram, [(4, 4)]);
+}
+
+#[test]
+fn peak_allocated_bytes() {
+    let mut bump = Bump::new();
+    assert_eq!(bump.peak_allocated_bytes(), 0);
+
+    bump.alloc(1_u8);
+    assert_eq!(bump.peak_allocated_bytes(), bump.allocated_bytes());
+
+    // Grow to several chunks.
+    for _ in 0..3 {
+        bump.alloc_slice_fill_copy(bump.chunk_capacity() + 1, 0_u8);
+    }
+    let peak = bump.allocated_bytes();
+    assert_eq!(bump.peak_allocated_bytes(), peak);
+
+    // The peak survives a reset, which frees all but the last chunk...
+    bump.reset();
+    assert!(bump.allocated_bytes() < peak);
+    assert_eq!(bump.peak_allocated_bytes(), peak);
+
+    // ...until it is explicitly reset.
+    bump.reset_peak();
+    assert_eq!(bump.peak_allocated_bytes(), bump.allocated_bytes());
+}
+
+#[test]
+fn peak_allocated_bytes_with_capacity() {
+    let bump = Bump::with_capacity(4096);
+    assert_eq!(bump.peak_allocated_bytes(), bump.allocated_bytes());
+    assert!(bump.peak_allocated_bytes() >= 4096);
+}
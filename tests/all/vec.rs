@@ -1,10 +1,21 @@
 #![cfg(feature = "collections")]
 
 use crate::quickcheck;
-use bumpalo::{collections::Vec, vec, Bump};
+use bumpalo::{
+    collections::{CollectIn, Vec},
+    vec, Bump,
+};
 use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 
+struct CountDrops<'a>(u32, &'a Cell<u32>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
 #[test]
 fn push_a_bunch_of_items() {
     let b = Bump::new();
@@ -61,6 +72,46 @@ fn test_into_bump_slice_mut() {
     assert_eq!(slice, [3, 2, 1]);
 }
 
+#[test]
+fn test_leak_outlives_vec() {
+    let b = Bump::new();
+    let slice = {
+        let mut v = Vec::with_capacity_in(4, &b);
+        v.extend([String::from("a"), String::from("b")]);
+        let ptr = v.as_ptr();
+        let slice = v.leak();
+        assert_eq!(slice.as_ptr(), ptr);
+        slice
+    };
+
+    // Allocating more doesn't disturb the leaked slice.
+    b.alloc_slice_fill_copy(1000, 0_u8);
+    slice[1].push('c');
+    assert_eq!(slice, ["a", "bc"]);
+
+    // The leaked elements are never dropped, so clean them up by hand.
+    for s in slice.iter_mut() {
+        drop(std::mem::take(s));
+    }
+}
+
+#[test]
+fn test_from_elem_in() {
+    let b = Bump::new();
+
+    let empty = Vec::from_elem_in(String::from("x"), 0, &b);
+    assert!(empty.is_empty());
+
+    let n = 100_000;
+    let v = Vec::from_elem_in(7_u32, n, &b);
+    assert_eq!(v.len(), n);
+    assert_eq!(v.capacity(), n);
+    assert!(v.iter().all(|&x| x == 7));
+
+    let strings = Vec::from_elem_in(String::from("ab"), 3, &b);
+    assert_eq!(strings, ["ab", "ab", "ab"]);
+}
+
 quickcheck! {
     fn vec_resizes_causing_reallocs(sizes: std::vec::Vec<usize>) -> () {
         // Exercise `realloc` by doing a bunch of `resize`s followed by
@@ -87,6 +138,62 @@ quickcheck! {
     }
 }
 
+fn used_bytes(b: &Bump) -> usize {
+    b.allocated_bytes_per_chunk().sum()
+}
+
+#[test]
+fn shrink_to_fit_at_tip_gives_capacity_back() {
+    let b = Bump::with_capacity(1024);
+    let mut v = Vec::with_capacity_in(100, &b);
+    v.extend(0..90_u32);
+    let before = used_bytes(&b);
+
+    // Less than half of the capacity is excess, which the allocator's own
+    // `shrink` would not bother reclaiming.
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 90);
+    assert_eq!(before - used_bytes(&b), 10 * std::mem::size_of::<u32>());
+    assert!(v.iter().copied().eq(0..90));
+
+    // The reclaimed space is reused by the next allocation.
+    let x = b.alloc(7_u32);
+    assert_eq!(x as *mut u32, unsafe { v.as_mut_ptr().sub(1) });
+}
+
+#[test]
+fn shrink_to_respects_lower_bound() {
+    let b = Bump::with_capacity(1024);
+    let mut v = Vec::with_capacity_in(100, &b);
+    v.extend(0..10_u64);
+    let before = used_bytes(&b);
+
+    v.shrink_to(50);
+    assert_eq!(v.capacity(), 50);
+    assert_eq!(before - used_bytes(&b), 50 * std::mem::size_of::<u64>());
+
+    // Shrinking to more than the current capacity is a no-op.
+    v.shrink_to(80);
+    assert_eq!(v.capacity(), 50);
+
+    v.shrink_to(0);
+    assert_eq!(v.capacity(), 10);
+    assert!(v.iter().copied().eq(0..10));
+}
+
+#[test]
+fn shrink_to_fit_not_at_tip_is_noop_for_arena() {
+    let b = Bump::with_capacity(1024);
+    let mut v = Vec::with_capacity_in(100, &b);
+    v.extend(0..10_u8);
+    b.alloc(0_u8);
+    let before = used_bytes(&b);
+
+    v.shrink_to_fit();
+    assert_eq!(used_bytes(&b), before);
+    assert!(v.iter().copied().eq(0..10));
+}
+
 #[test]
 fn test_vec_items_get_dropped() {
     struct Foo<'a>(&'a RefCell<String>);
@@ -123,6 +230,19 @@ fn test_extend_from_slice_copy() {
     assert_eq!(&[1, 2, 3, 4, 5, 6, 7][..], vec.as_slice());
 }
 
+#[test]
+fn test_extend_from_slice_copy_grows_once() {
+    let bump = Bump::new();
+    let src = std::vec![0xab_u8; 1 << 20];
+
+    let mut vec = Vec::<u8>::new_in(&bump);
+    vec.extend_from_slice_copy(&src);
+
+    // A single `reserve` for the whole slice leaves no slack behind.
+    assert_eq!(vec.capacity(), src.len());
+    assert_eq!(vec.as_slice(), &src[..]);
+}
+
 #[test]
 fn test_extend_from_slices_copy() {
     let bump = Bump::new();
@@ -167,3 +287,403 @@ fn test_vec_write() {
 
     assert_eq!(v, &[1, 2, 3]);
 }
+
+#[test]
+fn test_pop_and_swap_remove_move_elements_out() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    let mut vec = Vec::new_in(&bump);
+    for i in 0..4 {
+        vec.push(CountDrops(i, &drops));
+    }
+
+    let removed = vec.swap_remove(1);
+    assert_eq!(removed.0, 1);
+    assert_eq!(
+        vec.iter().map(|f| f.0).collect::<std::vec::Vec<_>>(),
+        [0, 3, 2]
+    );
+    assert_eq!(drops.get(), 0);
+    drop(removed);
+    assert_eq!(drops.get(), 1);
+
+    let popped = vec.pop().unwrap();
+    assert_eq!(popped.0, 2);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(drops.get(), 1);
+    drop(popped);
+    assert_eq!(drops.get(), 2);
+
+    assert_eq!(vec.swap_remove(1).0, 3);
+    assert_eq!(vec.pop().unwrap().0, 0);
+    assert!(vec.pop().is_none());
+    assert!(vec.is_empty());
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn test_clear_drops_elements_in_order_and_keeps_capacity() {
+    struct Foo<'a>(u32, &'a RefCell<std::vec::Vec<u32>>);
+    impl Drop for Foo<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    let dropped = RefCell::new(std::vec::Vec::new());
+    let bump = Bump::new();
+    let mut vec = Vec::new_in(&bump);
+    for i in 0..5 {
+        vec.push(Foo(i, &dropped));
+    }
+    let capacity = vec.capacity();
+    let ptr = vec.as_ptr();
+
+    vec.clear();
+    assert!(vec.is_empty());
+    assert_eq!(*dropped.borrow(), [0, 1, 2, 3, 4]);
+    assert_eq!(vec.capacity(), capacity);
+
+    vec.push(Foo(5, &dropped));
+    assert_eq!(vec.as_ptr(), ptr);
+    vec.truncate(0);
+    assert_eq!(*dropped.borrow(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_truncate_drops_the_rest_when_a_drop_panics() {
+    struct Foo<'a>(u32, &'a Cell<u32>);
+    impl Drop for Foo<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+            if self.0 == 2 {
+                panic!("drop panicked");
+            }
+        }
+    }
+
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    let mut vec = Vec::new_in(&bump);
+    for i in 0..5 {
+        vec.push(Foo(i, &drops));
+    }
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.truncate(1)));
+    assert!(result.is_err());
+    assert_eq!(vec.len(), 1);
+    assert_eq!(drops.get(), 4);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn test_append_moves_elements() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    let mut vec = Vec::new_in(&bump);
+    let mut other = Vec::new_in(&bump);
+    for i in 0..3 {
+        vec.push(CountDrops(i, &drops));
+        other.push(CountDrops(i + 3, &drops));
+    }
+
+    vec.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(
+        vec.iter().map(|f| f.0).collect::<std::vec::Vec<_>>(),
+        [0, 1, 2, 3, 4, 5]
+    );
+    assert_eq!(drops.get(), 0);
+
+    drop(other);
+    assert_eq!(drops.get(), 0);
+    drop(vec);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 7")]
+fn test_index_out_of_bounds() {
+    let bump = Bump::new();
+    let vec = vec![in &bump; 1, 2, 3];
+    let _ = vec[7];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn test_index_mut_out_of_bounds() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    vec[3] = 4;
+}
+
+#[test]
+fn test_get_and_get_mut() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    assert_eq!(vec.get(1), Some(&2));
+    assert_eq!(vec.get(3), None);
+    assert_eq!(vec.get(1..), Some(&[2, 3][..]));
+    assert_eq!(vec.get(2..5), None);
+
+    *vec.get_mut(0).unwrap() = 10;
+    assert!(vec.get_mut(3).is_none());
+    assert_eq!(vec, [10, 2, 3]);
+}
+
+#[test]
+fn test_sort_and_binary_search() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 5, 3, 8, 1, 9, 2];
+
+    vec.sort();
+    assert_eq!(vec, [1, 2, 3, 5, 8, 9]);
+    assert_eq!(vec.binary_search(&5), Ok(3));
+    assert_eq!(vec.binary_search(&4), Err(3));
+
+    vec.sort_by(|a, b| b.cmp(a));
+    assert_eq!(vec, [9, 8, 5, 3, 2, 1]);
+
+    vec.sort_unstable();
+    assert_eq!(vec, [1, 2, 3, 5, 8, 9]);
+
+    let mut words = vec![in &bump; "pear", "fig", "banana"];
+    words.sort_unstable_by_key(|w| w.len());
+    assert_eq!(words, ["fig", "pear", "banana"]);
+    assert_eq!(words.binary_search_by_key(&4, |w| w.len()), Ok(1));
+}
+
+#[test]
+fn test_into_iter_double_ended_and_exact_size() {
+    let bump = Bump::new();
+    let vec = vec![in &bump; 1, 2, 3, 4, 5];
+    assert_eq!(
+        vec.into_iter().rev().collect::<std::vec::Vec<_>>(),
+        [5, 4, 3, 2, 1]
+    );
+
+    let vec = vec![in &bump; 1, 2, 3, 4, 5];
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_into_iter_drops_unconsumed_elements() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    let mut vec = Vec::new_in(&bump);
+    for i in 0..6 {
+        vec.push(CountDrops(i, &drops));
+    }
+
+    let mut iter = vec.into_iter();
+    let first = iter.next().unwrap();
+    let last = iter.next_back().unwrap();
+    assert_eq!((first.0, last.0), (0, 5));
+    assert_eq!(drops.get(), 0);
+
+    drop(iter);
+    assert_eq!(drops.get(), 4);
+    drop((first, last));
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+#[should_panic]
+fn test_swap_remove_out_of_bounds() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    vec.swap_remove(3);
+}
+
+#[test]
+fn test_splice_longer_replacement() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3, 4, 5];
+    let removed: std::vec::Vec<_> = v.splice(1..3, [10, 11, 12, 13]).collect();
+    assert_eq!(removed, [2, 3]);
+    assert_eq!(v, [1, 10, 11, 12, 13, 4, 5]);
+}
+
+#[test]
+fn test_splice_shorter_replacement() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3, 4, 5];
+    let removed: std::vec::Vec<_> = v.splice(1..4, [10]).collect();
+    assert_eq!(removed, [2, 3, 4]);
+    assert_eq!(v, [1, 10, 5]);
+
+    let removed: std::vec::Vec<_> = v.splice(.., std::iter::empty()).collect();
+    assert_eq!(removed, [1, 10, 5]);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_splice_unknown_length_replacement() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3];
+    // `filter` has a lower size hint of zero, so the tail has to be moved
+    // again once the real length is known.
+    v.splice(1..2, (10..20).filter(|n| n % 2 == 0));
+    assert_eq!(v, [1, 10, 12, 14, 16, 18, 3]);
+}
+
+#[test]
+fn test_splice_dropped_early() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3, 4, 5];
+    {
+        let mut splice = v.splice(1..4, [7, 8]);
+        assert_eq!(splice.next(), Some(2));
+    }
+    assert_eq!(v, [1, 7, 8, 5]);
+
+    let drops = Cell::new(0);
+    let mut v = Vec::new_in(&bump);
+    for i in 0..5 {
+        v.push(CountDrops(i, &drops));
+    }
+    drop(v.splice(..3, std::iter::once(CountDrops(9, &drops))));
+    assert_eq!(drops.get(), 3);
+    assert!(v.iter().map(|d| d.0).eq([9, 3, 4]));
+}
+
+#[test]
+fn test_with_capacity_in_reserves_exactly() {
+    for n in [1, 7, 100, 4096] {
+        let bump = Bump::new();
+        let mut v: Vec<u32> = Vec::with_capacity_in(n, &bump);
+        assert_eq!(v.capacity(), n);
+
+        let allocated = bump.allocated_bytes();
+        let used: usize = bump.allocated_bytes_per_chunk().sum();
+        assert!(used >= n * std::mem::size_of::<u32>());
+
+        let ptr = v.as_ptr();
+        for i in 0..n as u32 {
+            v.push(i);
+        }
+        assert_eq!(v.as_ptr(), ptr, "n = {n}");
+        assert_eq!(v.capacity(), n);
+        assert_eq!(bump.allocated_bytes(), allocated);
+        assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), used);
+    }
+}
+
+#[test]
+fn test_extract_if_every_other_element() {
+    let bump = Bump::new();
+    let mut v: Vec<u32> = (0..10).collect_in(&bump);
+    let mut i = 0;
+    let removed: Vec<u32> = v
+        .extract_if(|_| {
+            i += 1;
+            i % 2 == 0
+        })
+        .collect_in(&bump);
+    assert_eq!(removed, [1, 3, 5, 7, 9]);
+    assert_eq!(v, [0, 2, 4, 6, 8]);
+}
+
+#[test]
+fn test_extract_if_drops_each_element_once() {
+    let bump = Bump::new();
+    let drops = RefCell::new(std::vec::Vec::new());
+    struct LogDrop<'a>(u32, &'a RefCell<std::vec::Vec<u32>>);
+    impl Drop for LogDrop<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    let mut v = Vec::new_in(&bump);
+    for i in 0..6 {
+        v.push(LogDrop(i, &drops));
+    }
+    for d in v.extract_if(|d| d.0 % 2 == 1) {
+        assert_eq!(d.0 % 2, 1);
+    }
+    assert_eq!(*drops.borrow(), [1, 3, 5]);
+    assert!(v.iter().map(|d| d.0).eq([0, 2, 4]));
+
+    drop(v);
+    assert_eq!(*drops.borrow(), [1, 3, 5, 0, 2, 4]);
+}
+
+#[test]
+fn test_extract_if_dropped_early_keeps_rest() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3, 4, 5, 6];
+    let mut calls = 0;
+    {
+        let mut iter = v.extract_if(|x| {
+            calls += 1;
+            *x % 2 == 0
+        });
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(4));
+    }
+    assert_eq!(calls, 4);
+    assert_eq!(v, [1, 3, 5, 6]);
+}
+
+#[test]
+fn test_extract_if_panicking_predicate() {
+    let bump = Bump::new();
+    let mut v = vec![in &bump; 1, 2, 3, 4, 5, 6];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        for _ in v.extract_if(|x| {
+            assert!(*x != 4);
+            *x % 2 == 0
+        }) {}
+    }));
+    assert!(result.is_err());
+    assert_eq!(v, [1, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_cross_type_equality() {
+    let bump = Bump::new();
+    let v = vec![in &bump; 1, 2, 3];
+
+    // Against `std::vec::Vec`, in both directions.
+    assert_eq!(v, std::vec![1, 2, 3]);
+    assert_eq!(std::vec![1, 2, 3], v);
+    assert_ne!(v, std::vec![1, 2]);
+    assert_ne!(std::vec![1, 2, 4], v);
+
+    // Against arrays, in both directions.
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!([1, 2, 3], v);
+    assert_ne!([3, 2, 1], v);
+    assert_eq!(v, &[1, 2, 3]);
+
+    // Against slices, in both directions.
+    let slice: &[i32] = &[1, 2, 3];
+    assert_eq!(v, slice);
+    assert_eq!(slice, v);
+    assert_eq!(v, *slice);
+    assert_eq!(*slice, v);
+    let mut array = [1, 2, 3];
+    let slice_mut: &mut [i32] = &mut array;
+    assert!(slice_mut == v);
+    assert!(v == slice_mut);
+
+    // Elements of different types are compared with their own `PartialEq`.
+    let strings = vec![in &bump; String::from("a"), String::from("b")];
+    assert_eq!(strings, ["a", "b"]);
+    assert_eq!(strings, std::vec!["a", "b"]);
+    assert_eq!(std::vec!["a", "b"], strings);
+}
@@ -0,0 +1,90 @@
+//! Tests that bumpalo's allocation API and collections can be used from a
+//! `#![no_std]` crate that only has access to `core` and `alloc`.
+//!
+//! Nothing here may name `std`: the test harness links `std` for us, but the
+//! code under test must only need what a `no_std` + `alloc` user would have.
+
+#![no_std]
+
+extern crate alloc;
+
+use bumpalo::{AllocErr, Bump};
+
+#[test]
+fn alloc() {
+    let bump = Bump::new();
+    let x = bump.alloc(42_u64);
+    assert_eq!(*x, 42);
+
+    let slice = bump.alloc_slice_copy(&[1, 2, 3]);
+    assert_eq!(slice, &[1, 2, 3]);
+
+    let s = bump.alloc_str("hello");
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn try_alloc() {
+    let bump = Bump::try_new().unwrap();
+    assert_eq!(bump.try_alloc(1_u8).map(|x| *x), Ok(1));
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    assert_eq!(
+        bump.try_alloc_slice_fill_copy(10_000, 0_u8).map(|_| ()),
+        Err(AllocErr)
+    );
+}
+
+#[test]
+fn reset() {
+    let mut bump = Bump::new();
+    bump.alloc_slice_fill_copy(1000, 0_u8);
+    bump.reset();
+    assert_eq!(bump.allocated_bytes_per_chunk().sum::<usize>(), 0);
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn vec() {
+    use bumpalo::collections::Vec;
+
+    let bump = Bump::new();
+    let mut v = Vec::new_in(&bump);
+    for i in 0..100 {
+        v.push(i);
+    }
+    v.extend_from_slice_copy(&[100, 101]);
+    assert_eq!(v.len(), 102);
+    assert_eq!(v.iter().sum::<i32>(), (0..102).sum());
+
+    let v = bumpalo::vec![in &bump; 1, 2, 3];
+    assert_eq!(v.into_bump_slice(), &[1, 2, 3]);
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn string() {
+    use bumpalo::collections::String;
+    use core::fmt::Write;
+
+    let bump = Bump::new();
+    let mut s = String::new_in(&bump);
+    s.push_str("hello");
+    let name = "world";
+    write!(s, ", {}!", name).unwrap();
+    assert_eq!(s.as_str(), "hello, world!");
+
+    let s = bumpalo::format!(in &bump, "{}-{}", 1, 2);
+    assert_eq!(s.into_bump_str(), "1-2");
+}
+
+#[cfg(feature = "boxed")]
+#[test]
+fn boxed() {
+    use bumpalo::boxed::Box;
+
+    let bump = Bump::new();
+    let b = Box::new_in(alloc::string::String::from("boxed"), &bump);
+    assert_eq!(b.as_str(), "boxed");
+}